@@ -0,0 +1,39 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_core::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use regular::DFA;
+
+fn random_workload(c: &mut Criterion) {
+    let mut group = c.benchmark_group("random");
+    for num_states in [10, 100, 1000] {
+        group.bench_with_input(
+            BenchmarkId::new("generate", num_states),
+            &num_states,
+            |b, &num_states| {
+                let mut rng = XorShiftRng::seed_from_u64(0);
+                b.iter(|| DFA::random('a'..='d', num_states, &mut rng));
+            },
+        );
+    }
+    group.finish();
+}
+
+fn products(c: &mut Criterion) {
+    let mut rng = XorShiftRng::seed_from_u64(0);
+    let mut group = c.benchmark_group("product");
+    for num_states in [10, 50] {
+        let left = DFA::random('a'..='d', num_states, &mut rng);
+        let right = DFA::random('a'..='d', num_states, &mut rng);
+
+        group.bench_function(BenchmarkId::new("intersection", num_states), |b| {
+            b.iter(|| -> DFA<_> { black_box(&left).intersection(black_box(&right)).unwrap() });
+        });
+        group.bench_function(BenchmarkId::new("is_equivalent", num_states), |b| {
+            b.iter(|| black_box(&left).is_equivalent(black_box(&right)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, random_workload, products);
+criterion_main!(benches);
@@ -1,14 +1,18 @@
-use crate::util::{Bounded, Range, Step};
+use crate::{
+    collections::BTreeSet,
+    util::{Bounded, Range, Step},
+};
+use alloc::vec::{IntoIter, Vec};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
 use core::{
-    hash::{BuildHasher, Hash},
-    iter::{once, Chain, Once},
+    fmt::Debug,
+    iter::{once, Chain, Map, Once},
     marker::PhantomData,
     ops,
 };
-use std::{
-    collections::{BTreeSet, HashSet},
-    vec::IntoIter,
-};
+#[cfg(feature = "std")]
+use std::collections::HashSet;
 
 /// A set of symbols.
 pub trait Alphabet {
@@ -29,6 +33,7 @@ pub trait Alphabet {
     fn num_values(&self) -> Option<usize>;
 }
 
+#[cfg(feature = "std")]
 impl<V, S> Alphabet for HashSet<V, S>
 where
     V: Hash + Eq + Clone,
@@ -50,6 +55,7 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<V, S> Alphabet for &HashSet<V, S>
 where
     V: Hash + Eq + Clone,
@@ -139,6 +145,7 @@ where
 
 /// Alphabet containing all booleans {True, False}.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Boolean;
 
 impl Alphabet for Boolean {
@@ -160,6 +167,7 @@ impl Alphabet for Boolean {
 
 /// Alphabet containing single symbol of the unit type.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Unit;
 
 impl Alphabet for Unit {
@@ -179,6 +187,128 @@ impl Alphabet for Unit {
     }
 }
 
+/// Alphabet of pairs of symbols, one from each of two alphabets, for reading
+/// two tracks at once.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Product<A, B>(pub A, pub B);
+
+impl<A, B> Alphabet for Product<A, B>
+where
+    A: Alphabet,
+    A::Symbol: Clone,
+    B: Alphabet + Clone,
+{
+    type Symbol = (A::Symbol, B::Symbol);
+    type ValueIter = ProductIter<A, B>;
+
+    fn values(&self) -> Self::ValueIter {
+        ProductIter {
+            left: self.0.values(),
+            current: None,
+            right: self.1.values(),
+            right_alphabet: self.1.clone(),
+        }
+    }
+
+    fn contains(&self, sym: &Self::Symbol) -> bool {
+        self.0.contains(&sym.0) && self.1.contains(&sym.1)
+    }
+
+    fn num_values(&self) -> Option<usize> {
+        // An empty factor makes the product empty, even if the other factor
+        // has too many values to count.
+        match (self.0.num_values(), self.1.num_values()) {
+            (Some(0), _) | (_, Some(0)) => Some(0),
+            (Some(left), Some(right)) => left.checked_mul(right),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator over the symbols of a `Product` alphabet, in order of the left
+/// symbol and then the right symbol.
+pub struct ProductIter<A: Alphabet, B: Alphabet> {
+    left: A::ValueIter,
+    current: Option<A::Symbol>,
+    right: B::ValueIter,
+    right_alphabet: B,
+}
+
+impl<A, B> Iterator for ProductIter<A, B>
+where
+    A: Alphabet,
+    A::Symbol: Clone,
+    B: Alphabet,
+{
+    type Item = (A::Symbol, B::Symbol);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(left) = self.current.as_ref() {
+                if let Some(right) = self.right.next() {
+                    return Some((left.clone(), right));
+                }
+            }
+
+            self.current = Some(self.left.next()?);
+            self.right = self.right_alphabet.values();
+        }
+    }
+}
+
+/// A symbol from one of the two alphabets of a `Sum` alphabet.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Either<L, R> {
+    /// A symbol of the left alphabet.
+    Left(L),
+    /// A symbol of the right alphabet.
+    Right(R),
+}
+
+/// Alphabet of the symbols of two alphabets, tagged with the alphabet they
+/// came from, for merging two streams of symbols.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sum<A, B>(pub A, pub B);
+
+/// Iterator over the symbols of a `Sum` alphabet, with every left symbol
+/// before every right symbol.
+pub type SumIter<A, B> = Chain<
+    Map<<A as Alphabet>::ValueIter, fn(<A as Alphabet>::Symbol) -> SumSymbol<A, B>>,
+    Map<<B as Alphabet>::ValueIter, fn(<B as Alphabet>::Symbol) -> SumSymbol<A, B>>,
+>;
+
+type SumSymbol<A, B> = Either<<A as Alphabet>::Symbol, <B as Alphabet>::Symbol>;
+
+impl<A, B> Alphabet for Sum<A, B>
+where
+    A: Alphabet,
+    B: Alphabet,
+{
+    type Symbol = SumSymbol<A, B>;
+    type ValueIter = SumIter<A, B>;
+
+    fn values(&self) -> Self::ValueIter {
+        let left: fn(A::Symbol) -> Self::Symbol = Either::Left;
+        let right: fn(B::Symbol) -> Self::Symbol = Either::Right;
+
+        self.0.values().map(left).chain(self.1.values().map(right))
+    }
+
+    fn contains(&self, sym: &Self::Symbol) -> bool {
+        match sym {
+            Either::Left(sym) => self.0.contains(sym),
+            Either::Right(sym) => self.1.contains(sym),
+        }
+    }
+
+    fn num_values(&self) -> Option<usize> {
+        self.0.num_values()?.checked_add(self.1.num_values()?)
+    }
+}
+
 /// Coversion into an alphabet.
 pub trait IntoAlphabet {
     /// The type of symbols in the alphabet.
@@ -296,3 +426,82 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dfa::DFABuilder;
+
+    #[test]
+    fn product_alphabet() {
+        let alphabet = Product(Boolean, ('a'..='c').into_alphabet());
+
+        assert_eq!(
+            alphabet.values().collect::<Vec<_>>(),
+            [
+                (false, 'a'),
+                (false, 'b'),
+                (false, 'c'),
+                (true, 'a'),
+                (true, 'b'),
+                (true, 'c')
+            ]
+        );
+        assert!(alphabet.contains(&(true, 'b')));
+        assert!(!alphabet.contains(&(true, 'd')));
+        assert_eq!(Product(Boolean, Unit).num_values(), Some(2));
+        assert_eq!(Product(Range::<u8>::Empty, Boolean).values().count(), 0);
+    }
+
+    #[test]
+    fn sum_alphabet() {
+        let alphabet = Sum(Unit, Boolean);
+
+        assert_eq!(
+            alphabet.values().collect::<Vec<_>>(),
+            [Either::Left(()), Either::Right(false), Either::Right(true)]
+        );
+        assert!(alphabet.contains(&Either::Right(true)));
+        assert_eq!(alphabet.num_values(), Some(3));
+    }
+
+    #[test]
+    fn num_values_overflow() {
+        let full = Range::NonEmpty {
+            start: 0,
+            end: usize::MAX,
+        };
+        // Too many values to count, however the range counts its own.
+        let huge = Product(full.clone(), full);
+        assert_eq!(huge.num_values(), None);
+
+        assert_eq!(Product(huge.clone(), Boolean).num_values(), None);
+        assert_eq!(Sum(huge.clone(), Boolean).num_values(), None);
+        assert_eq!(
+            Product(huge.clone(), Range::<u8>::Empty).num_values(),
+            Some(0)
+        );
+        assert_eq!(Product(Range::<u8>::Empty, huge).num_values(), Some(0));
+    }
+
+    #[test]
+    fn two_track_dfa() {
+        // Accept pairs of strings that are equal.
+        let mut builder = DFABuilder::new(Product(Boolean, Boolean));
+        let equal = builder.new_state();
+        let dead = builder.new_state();
+        for (left, right) in builder.alphabet().values() {
+            let next = if left == right { equal } else { dead };
+            builder.transition(equal, (left, right), next).unwrap();
+            builder.transition(dead, (left, right), dead).unwrap();
+        }
+        builder
+            .start_state(equal)
+            .dead_state(Some(dead))
+            .accept_states([equal]);
+        let dfa = builder.build().unwrap();
+
+        assert!(dfa.accept([(true, true), (false, false)]));
+        assert!(!dfa.accept([(true, true), (false, true)]));
+    }
+}
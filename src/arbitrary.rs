@@ -0,0 +1,282 @@
+use crate::{
+    alphabet::{Alphabet, Boolean, Product, Sum, Unit},
+    dfa::{DFABuilder, DFA},
+    util::{Range, Step},
+};
+use alloc::vec::Vec;
+use core::{fmt::Debug, hash::Hash, ops::RangeInclusive};
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    bool::weighted,
+    collection::vec,
+    strategy::{BoxedStrategy, Just, Strategy},
+};
+
+/// Parameters for generating random complete DFAs with proptest.
+///
+/// Generated DFAs shrink towards fewer states and towards transitions that
+/// lead to the first state.
+#[derive(Debug, Clone)]
+pub struct DFAParams {
+    num_states: RangeInclusive<usize>,
+    accept_probability: f64,
+    dead_state_probability: f64,
+}
+
+impl DFAParams {
+    /// Create the default parameters: between 1 and 8 states, each accepting
+    /// with probability 0.5, and a dead state with probability 0.25.
+    pub fn new() -> Self {
+        DFAParams {
+            num_states: 1..=8,
+            accept_probability: 0.5,
+            dead_state_probability: 0.25,
+        }
+    }
+
+    /// Set the range of the number of states, including the dead state.
+    ///
+    /// # Panics
+    /// This function will panic if the range allows zero states or is empty.
+    pub fn num_states(&mut self, num_states: RangeInclusive<usize>) -> &mut Self {
+        assert!(
+            *num_states.start() >= 1 && num_states.start() <= num_states.end(),
+            "a DFA needs at least one state"
+        );
+        self.num_states = num_states;
+
+        self
+    }
+
+    /// Set the probability that each state is an accept state.
+    pub fn accept_probability(&mut self, probability: f64) -> &mut Self {
+        self.accept_probability = probability;
+
+        self
+    }
+
+    /// Set the probability that the last state is made a dead state, which
+    /// loops to itself on every symbol.
+    pub fn dead_state_probability(&mut self, probability: f64) -> &mut Self {
+        self.dead_state_probability = probability;
+
+        self
+    }
+
+    /// Return a strategy that generates DFAs over the given alphabet.
+    pub fn strategy<A>(&self, alphabet: A) -> BoxedStrategy<DFA<A>>
+    where
+        A: Alphabet + Clone + Debug + 'static,
+        A::Symbol: Clone + Eq + Hash + Debug + 'static,
+    {
+        let num_symbols = alphabet.values().count();
+        let accept_probability = self.accept_probability;
+        let dead_state_probability = self.dead_state_probability;
+
+        self.num_states
+            .clone()
+            .prop_flat_map(move |num_states| {
+                (
+                    vec(0..num_states, num_states * num_symbols),
+                    vec(weighted(accept_probability), num_states),
+                    0..num_states,
+                    weighted(dead_state_probability),
+                )
+            })
+            .prop_map(move |(targets, accept, start, has_dead)| {
+                build(alphabet.clone(), &targets, &accept, start, has_dead)
+            })
+            .boxed()
+    }
+}
+
+impl Default for DFAParams {
+    fn default() -> Self {
+        DFAParams::new()
+    }
+}
+
+// Build a complete DFA from a row of targets for each state.
+fn build<A>(alphabet: A, targets: &[usize], accept: &[bool], start: usize, has_dead: bool) -> DFA<A>
+where
+    A: Alphabet,
+    A::Symbol: Clone + Eq + Hash,
+{
+    let mut builder = DFABuilder::new(alphabet);
+    let states: Vec<usize> = accept.iter().map(|_| builder.new_state()).collect();
+    let symbols: Vec<A::Symbol> = builder.alphabet().values().collect();
+    let dead = if has_dead { states.last() } else { None };
+
+    for (idx, state) in states.iter().enumerate() {
+        for (sym_idx, sym) in symbols.iter().enumerate() {
+            let target = match dead {
+                Some(dead) if dead == state => *dead,
+                _ => states[targets[idx * symbols.len() + sym_idx]],
+            };
+            builder.add_transition(*state, sym.clone(), target);
+        }
+    }
+
+    builder
+        .start_state(states[start])
+        .dead_state(dead.copied())
+        .accept_states(
+            states
+                .iter()
+                .zip(accept)
+                .filter(|(_, accepts)| **accepts)
+                .map(|(state, _)| *state),
+        );
+
+    builder.build_unchecked()
+}
+
+impl<A> Arbitrary for DFA<A>
+where
+    A: Arbitrary + Alphabet + Clone + Debug + 'static,
+    A::Symbol: Clone + Eq + Hash + Debug + 'static,
+{
+    type Parameters = DFAParams;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        any::<A>()
+            .prop_flat_map(move |alphabet| params.strategy(alphabet))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Boolean {
+    type Parameters = ();
+    type Strategy = Just<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        Just(Boolean)
+    }
+}
+
+impl Arbitrary for Unit {
+    type Parameters = ();
+    type Strategy = Just<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        Just(Unit)
+    }
+}
+
+impl<A, B> Arbitrary for Product<A, B>
+where
+    A: Arbitrary + 'static,
+    B: Arbitrary + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<A>(), any::<B>())
+            .prop_map(|(left, right)| Product(left, right))
+            .boxed()
+    }
+}
+
+impl<A, B> Arbitrary for Sum<A, B>
+where
+    A: Arbitrary + 'static,
+    B: Arbitrary + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<A>(), any::<B>())
+            .prop_map(|(left, right)| Sum(left, right))
+            .boxed()
+    }
+}
+
+/// Generates small non-empty ranges, with at most 4 values.
+impl<Sym> Arbitrary for Range<Sym>
+where
+    Sym: Step + Arbitrary + Debug + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Sym>(), 0..4_usize)
+            .prop_map(|(start, len)| {
+                // Shorten the range if it would step past the last value.
+                let end = (0..=len)
+                    .rev()
+                    .find_map(|steps| start.forward(steps))
+                    .unwrap_or_else(|| start.clone());
+
+                Range::NonEmpty { start, end }
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dfa::DFAStorage;
+    use proptest::{prop_assert, prop_assert_eq, proptest};
+
+    fn small_dfa() -> BoxedStrategy<DFA<Boolean>> {
+        DFAParams::new().num_states(1..=5).strategy(Boolean)
+    }
+
+    #[test]
+    fn respects_params() {
+        let mut params = DFAParams::new();
+        params
+            .num_states(3..=3)
+            .accept_probability(1.0)
+            .dead_state_probability(0.0);
+
+        proptest!(|(dfa in params.strategy(Boolean))| {
+            prop_assert_eq!(dfa.storage().all_states().len(), 3);
+            prop_assert_eq!(dfa.accept_states().len(), 3);
+            prop_assert!(dfa.dead_state().is_none());
+        });
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_alphabets(dfa in any::<DFA<Range<char>>>()) {
+            let num_symbols = dfa.alphabet().values().count();
+            prop_assert!((1..=4).contains(&num_symbols));
+            prop_assert!(dfa.into_builder().validate().missing_transitions().is_empty());
+        }
+
+        #[test]
+        fn combined_alphabets(dfa in any::<DFA<Sum<Product<Boolean, Range<u8>>, Unit>>>()) {
+            prop_assert!(dfa.complement().complement().is_equivalent(&dfa).unwrap());
+        }
+
+        #[test]
+        fn complement_is_an_involution(dfa in small_dfa()) {
+            prop_assert!(dfa.complement().complement().is_equivalent(&dfa).unwrap());
+        }
+
+        #[test]
+        fn union_is_commutative(left in small_dfa(), right in small_dfa()) {
+            let forward: DFA<Boolean> = left.union(&right).unwrap();
+            let backward: DFA<Boolean> = right.union(&left).unwrap();
+
+            prop_assert!(forward.is_equivalent(&backward).unwrap());
+        }
+
+        #[test]
+        fn de_morgan(left in small_dfa(), right in small_dfa()) {
+            let union: DFA<Boolean> = left.union(&right).unwrap();
+            let intersection: DFA<Boolean> = left
+                .complement()
+                .intersection(&right.complement())
+                .unwrap();
+
+            prop_assert!(union.complement().is_equivalent(&intersection).unwrap());
+        }
+    }
+}
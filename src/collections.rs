@@ -0,0 +1,13 @@
+// Collections used throughout the crate. Hash maps come from `std` when it is
+// available, and otherwise from `hashbrown`, which is only a dependency when
+// its feature is enabled.
+
+pub(crate) use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map, HashMap};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{hash_map, HashMap};
+
+#[cfg(not(any(feature = "std", feature = "hashbrown")))]
+compile_error!("either the `std` or the `hashbrown` feature must be enabled");
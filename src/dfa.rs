@@ -1,13 +1,36 @@
 use crate::{
     accept::Accept,
     alphabet::{Alphabet, IntoAlphabet},
+    collections::{BTreeMap, HashMap},
     error::Error,
     util::VecSet,
 };
-use core::hash::Hash;
-use std::collections::HashMap;
-
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt::Debug, hash::Hash};
+
+mod att;
+mod binary;
+mod buchi;
+mod codegen;
+mod conformance;
+mod dot;
+mod homomorphism;
 mod operations;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "serde")]
+mod serialize;
+mod synchronizing;
+mod utf8;
+mod validate;
+
+pub use att::SymbolTable;
+pub use binary::{BinaryAlphabet, BinaryDFAStorage};
+pub use buchi::BuchiAutomaton;
+pub use codegen::{CodegenStyle, RustSymbol};
+pub use validate::ValidationReport;
 
 /// Backend for the DFA struct.
 ///
@@ -46,7 +69,7 @@ pub trait DFAStorage<A: Alphabet> {
     /// implementations can bypass underlying safety checks when specific
     /// variants are upheld.
     /// 1. The `current` state must have been the result of a previous call to
-    /// `add_state`.
+    /// `add_state`, and must not have been removed since by `remove_state`.
     /// 2. The `sym` symbol must be a valid symbol contained in  the alphabet
     /// associated with this DFA.
     #[inline]
@@ -59,6 +82,57 @@ pub trait DFAStorage<A: Alphabet> {
 
     /// Record the given transition.
     fn add_transition(&mut self, from: Self::State, sym: A::Symbol, to: Self::State);
+
+    /// Record the given transition, returning the symbol and target of the
+    /// transition it replaced, if there was one. The symbol is returned so
+    /// that it is available without cloning.
+    ///
+    /// The default implementation calls `add_transition` and never reports a
+    /// replaced transition.
+    fn replace_transition(
+        &mut self,
+        from: Self::State,
+        sym: A::Symbol,
+        to: Self::State,
+    ) -> Option<(A::Symbol, Self::State)> {
+        self.add_transition(from, sym, to);
+
+        None
+    }
+
+    /// Remove the given state, along with every transition from or to it.
+    ///
+    /// # Error
+    ///
+    /// The default implementation always errors with
+    /// `Error::UnsupportedOperation`. Implementations should error if the
+    /// state is not valid.
+    fn remove_state(&mut self, state: Self::State) -> Result<(), Error> {
+        let _ = state;
+
+        Err(Error::UnsupportedOperation {
+            operation: "remove_state",
+        })
+    }
+
+    /// Remove the transition from `from` via the given symbol, returning the
+    /// state it led to if there was one.
+    ///
+    /// # Error
+    ///
+    /// The default implementation always errors with
+    /// `Error::UnsupportedOperation`.
+    fn remove_transition(
+        &mut self,
+        from: Self::State,
+        sym: A::Symbol,
+    ) -> Result<Option<Self::State>, Error> {
+        let _ = (from, sym);
+
+        Err(Error::UnsupportedOperation {
+            operation: "remove_transition",
+        })
+    }
 }
 
 /// A deterministic finite automaton.
@@ -68,6 +142,7 @@ pub struct DFA<A: Alphabet, S: DFAStorage<A> = DefaultDFAStorage<A, <A as Alphab
     dead: Option<S::State>,
     start: S::State,
     storage: S,
+    labels: BTreeMap<S::State, String>,
 }
 
 impl<A, S> DFA<A, S>
@@ -92,6 +167,18 @@ where
         &self.start
     }
 
+    /// The label of a state, if it was created with
+    /// `DFABuilder::new_named_state`.
+    ///
+    /// Labels are only used for display and never affect the language. States
+    /// of a product DFA are labelled with the formatted string `(l,r)` of the
+    /// labels of both sides, where an unlabelled state is shown by its
+    /// position and a missing transition by `_`. Labels are dropped by
+    /// `DFA::to_binary`, so a DFA loaded into a `BinaryDFAStorage` has none.
+    pub fn state_label(&self, state: &S::State) -> Option<&str> {
+        self.labels.get(state).map(String::as_str)
+    }
+
     /// Convert this DFA back into the DFABuilder form.
     pub fn into_builder(self) -> DFABuilder<A, S> {
         DFABuilder {
@@ -99,6 +186,8 @@ where
             dead: self.dead,
             accept: self.accept,
             start: Some(self.start),
+            labels: self.labels,
+            overwritten: Vec::new(),
         }
     }
 }
@@ -109,6 +198,27 @@ where
     S::State: Ord,
     A: Alphabet,
 {
+    /// Return a reference to the `DFAStorage` backing this DFA.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Return a reference to the `Alphabet` in the storage of this DFA.
+    pub fn alphabet(&self) -> &A {
+        self.storage.alphabet()
+    }
+
+    // The state reached from `state` via `sym` while reading a string, where
+    // the dead state is absorbing as `accept` stops reading there. `None` is
+    // a missing transition, which rejects the string.
+    pub(crate) fn step(&self, state: S::State, sym: A::Symbol) -> Option<S::State> {
+        if self.dead.as_ref() == Some(&state) {
+            Some(state)
+        } else {
+            self.storage.transition(state, sym)
+        }
+    }
+
     /// Accept or reject a string based on the content of this DFA.
     ///
     /// This will immediately reject any string that contains a symbol that is
@@ -182,11 +292,13 @@ where
 /// Default storage for a DFA.
 ///
 /// Consists of a Hashmap for storing transistions, and the set of states is a
-/// linear range.
+/// linear range. Removed states leave gaps in the range until `compact` is
+/// called.
 #[derive(Debug, Clone)]
 pub struct DefaultDFAStorage<A, S: Eq + Hash> {
     alphabet: A,
     next_state: usize,
+    removed: VecSet<usize>,
     transition: HashMap<(usize, S), usize>,
 }
 
@@ -200,9 +312,40 @@ where
         DefaultDFAStorage {
             alphabet,
             next_state: 0,
+            removed: VecSet::new(),
             transition: HashMap::new(),
         }
     }
+
+    /// Renumber the states so that they form the range `0..n` again, after
+    /// states were removed. Return the new number of each old state, indexed
+    /// by the old state, which is `None` for removed states.
+    ///
+    /// Transitions from or to a state that is not valid, which can only be
+    /// recorded through `add_transition`, are dropped.
+    pub fn compact(&mut self) -> Vec<Option<usize>> {
+        let mut mapping = Vec::with_capacity(self.next_state);
+        let mut next = 0;
+        for state in 0..self.next_state {
+            if self.removed.contains(&state) {
+                mapping.push(None);
+            } else {
+                mapping.push(Some(next));
+                next += 1;
+            }
+        }
+
+        let renumber = |state: usize| mapping.get(state).copied().flatten();
+        self.transition = self
+            .transition
+            .drain()
+            .filter_map(|((from, sym), to)| Some(((renumber(from)?, sym), renumber(to)?)))
+            .collect();
+        self.next_state = next;
+        self.removed.clear();
+
+        mapping
+    }
 }
 
 impl<A> DFAStorage<A> for DefaultDFAStorage<A, A::Symbol>
@@ -217,7 +360,9 @@ where
     }
 
     fn all_states(&self) -> Vec<Self::State> {
-        (0..self.next_state).collect()
+        (0..self.next_state)
+            .filter(|state| !self.removed.contains(state))
+            .collect()
     }
 
     fn all_transitions(&self) -> Vec<(Self::State, A::Symbol, Self::State)> {
@@ -242,8 +387,41 @@ where
         self.transition.insert((from, sym), to);
     }
 
+    fn replace_transition(
+        &mut self,
+        from: Self::State,
+        sym: A::Symbol,
+        to: Self::State,
+    ) -> Option<(A::Symbol, Self::State)> {
+        let key = (from, sym);
+        let previous = self.transition.remove_entry(&key);
+        self.transition.insert(key, to);
+
+        previous.map(|((_, sym), previous)| (sym, previous))
+    }
+
     fn contains_state(&self, state: &Self::State) -> bool {
-        *state < self.next_state
+        *state < self.next_state && !self.removed.contains(state)
+    }
+
+    fn remove_state(&mut self, state: Self::State) -> Result<(), Error> {
+        if !self.contains_state(&state) {
+            return Err(Error::invalid_state(&state));
+        }
+
+        self.transition
+            .retain(|(from, _), to| *from != state && *to != state);
+        self.removed.insert(state);
+
+        Ok(())
+    }
+
+    fn remove_transition(
+        &mut self,
+        from: Self::State,
+        sym: A::Symbol,
+    ) -> Result<Option<Self::State>, Error> {
+        Ok(self.transition.remove(&(from, sym)))
     }
 
     fn alphabet(&self) -> &A {
@@ -259,6 +437,9 @@ pub struct DFABuilder<A: Alphabet, S: DFAStorage<A> = DefaultDFAStorage<A, <A as
     dead: Option<S::State>,
     start: Option<S::State>,
     storage: S,
+    labels: BTreeMap<S::State, String>,
+    // Transitions that replaced an earlier transition to a different state.
+    overwritten: Vec<validate::Overwrite<S::State, A::Symbol>>,
 }
 
 impl<A> DFABuilder<A>
@@ -273,8 +454,35 @@ where
             dead: None,
             start: None,
             storage: DefaultDFAStorage::new(alphabet.into_alphabet()),
+            labels: BTreeMap::new(),
+            overwritten: Vec::new(),
         }
     }
+
+    /// Renumber the states so that they form the range `0..n` again, after
+    /// states were removed, see `DefaultDFAStorage::compact`. The start, dead
+    /// and accept states are renumbered to match.
+    pub fn compact(&mut self) -> Vec<Option<usize>> {
+        let mapping = self.storage.compact();
+        let renumber = |state: &usize| mapping.get(*state).copied().flatten();
+
+        self.start = self.start.as_ref().and_then(renumber);
+        self.dead = self.dead.as_ref().and_then(renumber);
+        self.accept = self.accept.iter().filter_map(renumber).collect();
+        self.labels = core::mem::take(&mut self.labels)
+            .into_iter()
+            .filter_map(|(state, label)| Some((renumber(&state)?, label)))
+            .collect();
+        self.overwritten = self
+            .overwritten
+            .drain(..)
+            .filter_map(|(from, sym, previous, to)| {
+                Some((renumber(&from)?, sym, renumber(&previous)?, renumber(&to)?))
+            })
+            .collect();
+
+        mapping
+    }
 }
 
 impl<A, S> DFABuilder<A, S>
@@ -290,6 +498,8 @@ where
             accept: VecSet::new(),
             dead: None,
             start: None,
+            labels: BTreeMap::new(),
+            overwritten: Vec::new(),
         }
     }
 
@@ -308,6 +518,21 @@ where
         self.storage.add_state()
     }
 
+    /// Record and return a new state with a label, which is kept in the DFA
+    /// and used when displaying it. Labels are display-only, see
+    /// `DFA::state_label`.
+    pub fn new_named_state(&mut self, label: impl Into<String>) -> S::State {
+        let state = self.storage.add_state();
+        self.labels.insert(state.clone(), label.into());
+
+        state
+    }
+
+    /// The label of a state, if it was created with `new_named_state`.
+    pub fn state_label(&self, state: &S::State) -> Option<&str> {
+        self.labels.get(state).map(String::as_str)
+    }
+
     /// Record and validate a new transition.
     ///
     /// # Error
@@ -316,18 +541,33 @@ where
     /// alphabet. This function will error if either the `from` or `to` state is
     /// not a valid state (valid states are only returned from calls of the
     /// `add_state` function).
-    pub fn transition(
-        &mut self,
-        from: S::State,
-        sym: A::Symbol,
-        to: S::State,
-    ) -> Result<(), Error> {
-        if !self.storage.contains_state(&from) || !self.storage.contains_state(&to) {
-            Err(Error::InvalidState)
+    ///
+    /// A transition from the same state on the same symbol replaces the
+    /// earlier one, which is reported by `validate`.
+    ///
+    /// States and symbols must implement `Debug`, so that errors can name the
+    /// offending state or symbol.
+    pub fn transition(&mut self, from: S::State, sym: A::Symbol, to: S::State) -> Result<(), Error>
+    where
+        S::State: Debug,
+        A::Symbol: Debug,
+    {
+        if !self.storage.contains_state(&from) {
+            Err(Error::invalid_state(&from))
+        } else if !self.storage.contains_state(&to) {
+            Err(Error::invalid_state(&to))
         } else if !self.storage.alphabet().contains(&sym) {
-            Err(Error::SymbolNotInAlphabet)
+            Err(Error::symbol_not_in_alphabet(&sym))
         } else {
-            self.storage.add_transition(from, sym, to);
+            match self
+                .storage
+                .replace_transition(from.clone(), sym, to.clone())
+            {
+                Some((sym, previous)) if previous != to => {
+                    self.overwritten.push((from, sym, previous, to));
+                }
+                _ => {}
+            }
 
             Ok(())
         }
@@ -338,13 +578,25 @@ where
     /// # Error
     ///
     /// See the Error documentation of `transition` for ways that this function
-    /// can fail.
+    /// can fail. The error is wrapped in `Error::InTransitionBatch`, along
+    /// with the index of the failing transition. Transitions before that one
+    /// are still recorded.
+    /// Like `transition`, this requires states and symbols to implement
+    /// `Debug`.
     pub fn transitions(
         &mut self,
         transitions: impl IntoIterator<Item = (S::State, A::Symbol, S::State)>,
-    ) -> Result<(), Error> {
-        for (from, sym, to) in transitions {
-            self.transition(from, sym, to)?;
+    ) -> Result<(), Error>
+    where
+        S::State: Debug,
+        A::Symbol: Debug,
+    {
+        for (index, (from, sym, to)) in transitions.into_iter().enumerate() {
+            self.transition(from, sym, to)
+                .map_err(|source| Error::InTransitionBatch {
+                    index,
+                    source: Box::new(source),
+                })?;
         }
 
         Ok(())
@@ -374,26 +626,99 @@ where
         self
     }
 
+    /// Remove a state along with every transition from or to it. If it was
+    /// the start or dead state, that is unset.
+    ///
+    /// # Error
+    ///
+    /// This function will error if the state is not valid, or if the storage
+    /// does not support removing states.
+    pub fn remove_state(&mut self, state: S::State) -> Result<(), Error> {
+        self.storage.remove_state(state.clone())?;
+
+        self.accept.remove(&state);
+        if self.start.as_ref() == Some(&state) {
+            self.start = None;
+        }
+        if self.dead.as_ref() == Some(&state) {
+            self.dead = None;
+        }
+        self.labels.remove(&state);
+        self.overwritten
+            .retain(|(from, _, previous, to)| ![from, previous, to].contains(&&state));
+
+        Ok(())
+    }
+
+    /// Remove the transition from the given state via the given symbol,
+    /// returning the state it led to if there was one. Earlier overwrites of
+    /// the transition are forgotten.
+    ///
+    /// # Error
+    ///
+    /// This function will error if the state is not valid or the symbol is
+    /// not in the alphabet, or if the storage does not support removing
+    /// transitions.
+    pub fn remove_transition(
+        &mut self,
+        from: S::State,
+        sym: A::Symbol,
+    ) -> Result<Option<S::State>, Error>
+    where
+        S::State: Debug,
+        A::Symbol: Clone + Debug,
+    {
+        if !self.storage.contains_state(&from) {
+            Err(Error::invalid_state(&from))
+        } else if !self.storage.alphabet().contains(&sym) {
+            Err(Error::symbol_not_in_alphabet(&sym))
+        } else {
+            let removed = self.storage.remove_transition(from.clone(), sym)?;
+
+            // The only overwrite records from this state whose transition is
+            // now missing are those of the removed transition.
+            let storage = &self.storage;
+            self.overwritten.retain(|(other, sym, ..)| {
+                *other != from || storage.transition(other.clone(), sym.clone()).is_some()
+            });
+
+            Ok(removed)
+        }
+    }
+
+    /// Remove a state from the set of accept states.
+    pub fn remove_accept_state(&mut self, state: &S::State) -> &mut Self {
+        self.accept.remove(state);
+
+        self
+    }
+
     /// Build the DFA
-    pub fn build(self) -> Result<DFA<A, S>, Error> {
+    pub fn build(self) -> Result<DFA<A, S>, Error>
+    where
+        S::State: Debug,
+    {
         let DFABuilder {
             start,
             storage,
             dead,
             accept,
+            labels,
+            ..
         } = self;
         let start = start.ok_or(Error::MissingStartState)?;
 
-        if dead
-            .as_ref()
-            .map_or(false, |dead| !storage.contains_state(dead))
-        {
-            return Err(Error::InvalidState);
+        if !storage.contains_state(&start) {
+            return Err(Error::invalid_state(&start));
+        }
+
+        if let Some(dead) = dead.as_ref().filter(|dead| !storage.contains_state(dead)) {
+            return Err(Error::invalid_state(dead));
         }
 
         for state in &accept {
             if !storage.contains_state(state) {
-                return Err(Error::InvalidState);
+                return Err(Error::invalid_state(state));
             }
         }
 
@@ -402,8 +727,28 @@ where
             dead,
             accept,
             storage,
+            labels,
         })
     }
+
+    // Record a transition without checking it, for constructions whose states
+    // and symbols are valid by construction.
+    pub(crate) fn add_transition(&mut self, from: S::State, sym: A::Symbol, to: S::State) {
+        self.storage.add_transition(from, sym, to);
+    }
+
+    // Build the DFA without checking the start, dead and accept states, for
+    // constructions that only use states they created. The start state must
+    // have been set.
+    pub(crate) fn build_unchecked(self) -> DFA<A, S> {
+        DFA {
+            start: self.start.expect("constructions set a start state"),
+            dead: self.dead,
+            accept: self.accept,
+            storage: self.storage,
+            labels: self.labels,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -500,4 +845,105 @@ mod tests {
         assert!(dfa.accept("abbaa".chars()));
         assert!(dfa.accept("abcbaa".chars()));
     }
+
+    #[test]
+    fn errors_carry_context() {
+        let mut builder = DFABuilder::new('a'..='c');
+        let s0 = builder.new_state();
+
+        let err = builder
+            .transitions(vec![(s0, 'a', s0), (s0, 'z', s0)])
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::InTransitionBatch { index: 1, source }
+                if matches!(&**source, Error::SymbolNotInAlphabet { symbol } if symbol == "'z'")
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Transition 1 of the batch was invalid: Symbol 'z' not found in alphabet."
+        );
+        assert!(std::error::Error::source(&err).is_some());
+
+        builder.start_state(5);
+        assert!(matches!(
+            builder.build(),
+            Err(Error::InvalidState { state }) if state == "5"
+        ));
+    }
+
+    #[test]
+    fn remove_and_compact() {
+        let mut builder = simple_dfa().into_builder();
+
+        // Drop `b`, so the language becomes a*c*.
+        assert_eq!(builder.remove_transition(0, 'b').unwrap(), Some(1));
+        assert_eq!(builder.remove_transition(0, 'b').unwrap(), None);
+        builder.remove_state(1).unwrap();
+        assert!(matches!(
+            builder.remove_state(1),
+            Err(Error::InvalidState { .. })
+        ));
+        assert!(builder.transition(1, 'a', 0).is_err());
+        for sym in "abc".chars() {
+            assert_eq!(builder.storage().transition(1, sym), None);
+        }
+        assert_eq!(builder.storage().all_states(), vec![0, 2, 3]);
+
+        let mapping = builder.compact();
+        assert_eq!(mapping, vec![Some(0), None, Some(1), Some(2)]);
+        assert_eq!(builder.storage().all_states(), vec![0, 1, 2]);
+
+        let dfa = builder.clone().build().unwrap();
+        assert_eq!(dfa.dead_state(), Some(&2));
+        assert_eq!(dfa.accept_states(), &[0, 1]);
+        assert!(dfa.accept("aacc".chars()));
+        assert!(!dfa.accept("ab".chars()));
+        assert!(!dfa.accept("ca".chars()));
+
+        builder.remove_accept_state(&0);
+        let dfa = builder.build().unwrap();
+        assert!(!dfa.accept("aa".chars()));
+        assert!(dfa.accept("ac".chars()));
+    }
+
+    #[test]
+    fn compact_drops_transitions_with_removed_states() {
+        let mut builder = DFABuilder::new('a'..='c');
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        let q2 = builder.new_state();
+        builder.remove_state(q1).unwrap();
+
+        // Unchecked transitions can still use removed or unknown states.
+        builder.add_transition(q0, 'a', q2);
+        builder.add_transition(q0, 'b', q1);
+        builder.add_transition(q1, 'a', q0);
+        builder.add_transition(q2, 'c', 7);
+
+        assert_eq!(builder.compact(), vec![Some(0), None, Some(1)]);
+        assert_eq!(builder.storage().all_states(), vec![0, 1]);
+        assert_eq!(builder.storage().all_transitions(), vec![(0, 'a', 1)]);
+    }
+
+    #[test]
+    fn named_states() {
+        let mut builder = DFABuilder::new('a'..='b');
+        let waiting = builder.new_named_state("AwaitingAck");
+        let done = builder.new_state();
+        builder
+            .transitions(vec![(waiting, 'a', done), (waiting, 'b', waiting)])
+            .unwrap();
+        builder.start_state(waiting).accept_states(vec![done]);
+
+        let dfa = builder.build().unwrap();
+        assert_eq!(dfa.state_label(&waiting), Some("AwaitingAck"));
+        assert_eq!(dfa.state_label(&done), None);
+        assert!(format!("{:?}", dfa).contains("AwaitingAck"));
+
+        let mut builder = dfa.into_builder();
+        assert_eq!(builder.state_label(&waiting), Some("AwaitingAck"));
+        builder.remove_state(waiting).unwrap();
+        assert_eq!(builder.state_label(&waiting), None);
+    }
 }
@@ -0,0 +1,491 @@
+use crate::{
+    alphabet::{Alphabet, IntoAlphabet},
+    collections::{BTreeMap, HashMap},
+    dfa::{DFABuilder, DFAStorage, DFA},
+    error::Error,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    fmt::{Debug, Display, Write},
+    hash::Hash,
+    str::FromStr,
+};
+
+/// A mapping between symbols and the string labels that represent them in
+/// text formats.
+///
+/// Labels must not contain whitespace, as that separates the fields of a
+/// line.
+#[derive(Debug, Clone)]
+pub struct SymbolTable<Sym: Eq + Hash> {
+    labels: HashMap<Sym, String>,
+    symbols: HashMap<String, Sym>,
+}
+
+impl<Sym> SymbolTable<Sym>
+where
+    Sym: Clone + Eq + Hash,
+{
+    /// Create an empty symbol table.
+    pub fn new() -> Self {
+        SymbolTable {
+            labels: HashMap::new(),
+            symbols: HashMap::new(),
+        }
+    }
+
+    /// Create a symbol table that labels every symbol of the alphabet with its
+    /// `Display` representation.
+    ///
+    /// Whitespace and backslashes in the representation are escaped as
+    /// `\u{..}` and `\\`, so that a space is labelled `\u{20}`.
+    pub fn from_alphabet<A>(alphabet: &A) -> Self
+    where
+        A: Alphabet<Symbol = Sym>,
+        Sym: Display,
+    {
+        let mut table = SymbolTable::new();
+        for sym in alphabet.values() {
+            let label = escape(&sym.to_string());
+            table.insert(sym, label);
+        }
+
+        table
+    }
+
+    /// Parse a symbol table in the OpenFST text format, with a label and the
+    /// symbol it stands for on each line.
+    ///
+    /// The symbol field is unescaped like the labels of `from_alphabet`
+    /// before it is parsed, so `\u{20}` stands for a space.
+    ///
+    /// # Error
+    ///
+    /// This function will error if a line does not have exactly two fields,
+    /// or if a symbol is wrongly escaped or cannot be parsed.
+    pub fn parse(text: &str) -> Result<Self, Error>
+    where
+        Sym: FromStr,
+    {
+        let mut table = SymbolTable::new();
+
+        for (idx, line) in text.lines().enumerate() {
+            let malformed = Error::MalformedLine { line: idx + 1 };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [] => continue,
+                [label, sym] => {
+                    let sym = unescape(sym)
+                        .and_then(|sym| sym.parse().ok())
+                        .ok_or(malformed)?;
+                    table.insert(sym, *label);
+                }
+                _ => return Err(malformed),
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Write this symbol table in the format read by `SymbolTable::parse`,
+    /// sorted by label, escaping whitespace and backslashes in the symbols.
+    pub fn to_text(&self) -> String
+    where
+        Sym: Display,
+    {
+        let mut entries: Vec<(&String, &Sym)> = self.symbols.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut text = String::new();
+        for (label, sym) in entries {
+            writeln!(text, "{}\t{}", label, escape(&sym.to_string())).unwrap();
+        }
+
+        text
+    }
+
+    /// Label the given symbol, replacing any previous label of the symbol and
+    /// any previous symbol with that label.
+    pub fn insert(&mut self, sym: Sym, label: impl Into<String>) -> &mut Self {
+        let label = label.into();
+
+        if let Some(old_label) = self.labels.remove(&sym) {
+            self.symbols.remove(&old_label);
+        }
+        if let Some(old_sym) = self.symbols.remove(&label) {
+            self.labels.remove(&old_sym);
+        }
+
+        self.labels.insert(sym.clone(), label.clone());
+        self.symbols.insert(label, sym);
+
+        self
+    }
+
+    /// Return the label of the given symbol.
+    pub fn label(&self, sym: &Sym) -> Option<&str> {
+        self.labels.get(sym).map(String::as_str)
+    }
+
+    /// Return the symbol with the given label.
+    pub fn symbol(&self, label: &str) -> Option<&Sym> {
+        self.symbols.get(label)
+    }
+}
+
+// Escape whitespace as `\u{..}` and backslashes as `\\`, so that the text
+// forms a single field of a line.
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_whitespace() => write!(escaped, "\\u{{{:x}}}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+// Undo `escape`, returning `None` for a malformed escape sequence.
+fn unescape(text: &str) -> Option<String> {
+    let mut unescaped = String::new();
+    let mut rest = text;
+    while let Some(idx) = rest.find('\\') {
+        unescaped.push_str(&rest[..idx]);
+        rest = &rest[idx + 1..];
+        if let Some(after) = rest.strip_prefix('\\') {
+            unescaped.push('\\');
+            rest = after;
+        } else {
+            let (hex, after) = rest.strip_prefix("u{")?.split_once('}')?;
+            unescaped.push(char::from_u32(u32::from_str_radix(hex, 16).ok()?)?);
+            rest = after;
+        }
+    }
+    unescaped.push_str(rest);
+
+    Some(unescaped)
+}
+
+impl<Sym> Default for SymbolTable<Sym>
+where
+    Sym: Clone + Eq + Hash,
+{
+    fn default() -> Self {
+        SymbolTable::new()
+    }
+}
+
+impl<A, S> DFABuilder<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone + Eq + Hash,
+{
+    /// Read an automaton in the AT&T (OpenFST acceptor) text format.
+    ///
+    /// Each line is either a transition `from to label [weight]` or a final
+    /// state `state [weight]`. States are non-negative integers and labels are
+    /// looked up in the symbol table. Weights are checked to be numbers, then
+    /// ignored, except that a final weight of infinity means the state is not
+    /// final, as in the tropical semiring. The source of the first line is the
+    /// start state; an empty text gives a single start state that rejects
+    /// every string.
+    ///
+    /// # Error
+    ///
+    /// This function will error if a line cannot be parsed, if a label is not
+    /// in the symbol table or its symbol is not in the alphabet, or if a state
+    /// has two transitions on the same symbol.
+    pub fn from_att<I: IntoAlphabet<IntoAlpha = A, Symbol = A::Symbol>>(
+        alphabet: I,
+        text: &str,
+        symbols: &SymbolTable<A::Symbol>,
+    ) -> Result<Self, Error>
+    where
+        A::Symbol: Debug,
+    {
+        let mut builder = DFABuilder::new_with_storage(S::from_alphabet(alphabet.into_alphabet()));
+        let mut states: HashMap<usize, S::State> = HashMap::new();
+        let mut state = |builder: &mut Self, id: usize| {
+            states
+                .entry(id)
+                .or_insert_with(|| builder.new_state())
+                .clone()
+        };
+        let mut start = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let line_num = idx + 1;
+            let malformed = || Error::MalformedLine { line: line_num };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let parse_state = |field: &str| field.parse::<usize>().map_err(|_| malformed());
+            let parse_weight = |field: &str| field.parse::<f64>().map_err(|_| malformed());
+
+            match fields.as_slice() {
+                [] => continue,
+                [from, to, label] | [from, to, label, _] => {
+                    if let [_, _, _, weight] = fields.as_slice() {
+                        parse_weight(weight)?;
+                    }
+
+                    let from_id = parse_state(from)?;
+                    let from = state(&mut builder, from_id);
+                    let to = state(&mut builder, parse_state(to)?);
+                    let unknown = || Error::UnknownSymbol {
+                        line: line_num,
+                        label: label.to_string(),
+                    };
+                    let sym = symbols.symbol(label).ok_or_else(unknown)?.clone();
+
+                    if !builder.alphabet().contains(&sym) {
+                        return Err(unknown());
+                    }
+                    if builder
+                        .storage()
+                        .transition(from.clone(), sym.clone())
+                        .is_some()
+                    {
+                        return Err(Error::nondeterministic(&from_id, &sym, Some(line_num)));
+                    }
+
+                    start.get_or_insert_with(|| from.clone());
+                    builder.add_transition(from, sym, to);
+                }
+                [final_state] | [final_state, _] => {
+                    let weight = match fields.as_slice() {
+                        [_, weight] => parse_weight(weight)?,
+                        _ => 0.0,
+                    };
+
+                    let final_state = state(&mut builder, parse_state(final_state)?);
+                    start.get_or_insert_with(|| final_state.clone());
+                    if weight != f64::INFINITY {
+                        builder.accept_states(Some(final_state));
+                    }
+                }
+                _ => return Err(malformed()),
+            }
+        }
+
+        let start = match start {
+            Some(start) => start,
+            None => builder.new_state(),
+        };
+        builder.start_state(start);
+
+        Ok(builder)
+    }
+}
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone + Eq + Hash,
+{
+    /// Read a DFA in the AT&T text format, see `DFABuilder::from_att`.
+    ///
+    /// # Error
+    ///
+    /// See the Error documentation of `DFABuilder::from_att`.
+    pub fn from_att<I: IntoAlphabet<IntoAlpha = A, Symbol = A::Symbol>>(
+        alphabet: I,
+        text: &str,
+        symbols: &SymbolTable<A::Symbol>,
+    ) -> Result<Self, Error>
+    where
+        A::Symbol: Debug,
+    {
+        Ok(DFABuilder::from_att(alphabet, text, symbols)?.build_unchecked())
+    }
+
+    /// Write this DFA in the AT&T text format, read by `DFA::from_att`.
+    ///
+    /// States are numbered from `0`, which is the start state, and the dead
+    /// state is written as an ordinary state.
+    ///
+    /// # Error
+    ///
+    /// This function will error if a symbol on a transition has no label in
+    /// the symbol table.
+    pub fn to_att(&self, symbols: &SymbolTable<A::Symbol>) -> Result<String, Error>
+    where
+        A::Symbol: Debug,
+    {
+        let mut states = vec![self.start.clone()];
+        states.extend(
+            self.storage
+                .all_states()
+                .into_iter()
+                .filter(|state| *state != self.start),
+        );
+        let indices: BTreeMap<&S::State, usize> = states
+            .iter()
+            .enumerate()
+            .map(|(idx, state)| (state, idx))
+            .collect();
+        let index = |state: &S::State| indices[state];
+
+        let mut lines = Vec::new();
+        for (from_idx, state) in states.iter().enumerate() {
+            for sym in self.alphabet().values() {
+                if let Some(to) = self.storage.transition(state.clone(), sym.clone()) {
+                    let label = symbols.label(&sym).ok_or_else(|| Error::UnknownSymbol {
+                        line: lines.len() + 1,
+                        label: format!("{:?}", sym),
+                    })?;
+                    lines.push(format!("{}\t{}\t{}", from_idx, index(&to), label));
+                }
+            }
+        }
+
+        // The start state is only implied by the first line, so when it has
+        // no transitions its language is written directly: either the empty
+        // string or nothing at all.
+        if !lines.first().is_some_and(|line| line.starts_with("0\t")) {
+            return Ok(if self.accept.contains(&self.start) {
+                "0\n".to_string()
+            } else {
+                String::new()
+            });
+        }
+        let mut finals: Vec<usize> = self.accept.iter().map(index).collect();
+        finals.sort_unstable();
+        lines.extend(finals.into_iter().map(|idx| idx.to_string()));
+
+        let mut text = lines.join("\n");
+        text.push('\n');
+
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alphabet::Boolean, test_helper::CONTAINS_TWO_FALSE_DFA, util::Range};
+
+    fn letters() -> SymbolTable<char> {
+        SymbolTable::from_alphabet(&Range::NonEmpty {
+            start: 'a',
+            end: 'c',
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let symbols = SymbolTable::from_alphabet(&Boolean);
+        let text = CONTAINS_TWO_FALSE_DFA.to_att(&symbols).unwrap();
+
+        assert_eq!(
+            text,
+            "0\t1\tfalse\n0\t0\ttrue\n1\t2\tfalse\n1\t0\ttrue\n2\t2\tfalse\n2\t2\ttrue\n2\n"
+        );
+
+        let dfa: DFA<Boolean> = DFA::from_att(Boolean, &text, &symbols).unwrap();
+        assert!(dfa.is_equivalent(&*CONTAINS_TWO_FALSE_DFA).unwrap());
+    }
+
+    #[test]
+    fn read_with_weights_and_gaps() {
+        let text = "7 3 a\n3 3 b 0.5\n\n3 9 c\n9 1.0\n3\n";
+        let dfa: DFA<Range<char>> = DFA::from_att('a'..='c', text, &letters()).unwrap();
+
+        assert!(dfa.accept("ab".chars()));
+        assert!(dfa.accept("abbc".chars()));
+        assert!(!dfa.accept("b".chars()));
+        assert!(!dfa.accept("abca".chars()));
+
+        let dfa: DFA<Range<char>> =
+            DFA::from_att('a'..='c', "0 1 a\n0 2 b\n1 inf\n2 Infinity\n", &letters()).unwrap();
+        assert!(!dfa.accept("a".chars()));
+        assert!(!dfa.accept("b".chars()));
+    }
+
+    #[test]
+    fn structured_errors() {
+        let read = |text: &str| DFA::<Range<char>>::from_att('a'..='c', text, &letters());
+
+        assert!(matches!(
+            read("0 1 a\n1 x a\n"),
+            Err(Error::MalformedLine { line: 2 })
+        ));
+        assert!(matches!(
+            read("0 1 a\n1 2 a b c\n"),
+            Err(Error::MalformedLine { line: 2 })
+        ));
+        assert!(matches!(
+            read("0 1 a\n1 heavy\n"),
+            Err(Error::MalformedLine { line: 2 })
+        ));
+        assert!(matches!(
+            read("0 1 a\n1 2 z\n"),
+            Err(Error::UnknownSymbol { line: 2, ref label }) if label == "z"
+        ));
+        assert!(matches!(
+            read("0 1 a\n0 2 b\n0 0 a\n"),
+            Err(Error::Nondeterministic { line: Some(3), ref state, ref symbol }) if state == "0" && symbol == "'a'"
+        ));
+
+        let empty = read("").unwrap();
+        assert!(!empty.accept("".chars()));
+    }
+
+    #[test]
+    fn symbol_table_text() {
+        let table: SymbolTable<u32> = SymbolTable::parse("<eps> 0\nx 1\n\ny 2\n").unwrap();
+
+        assert_eq!(table.symbol("x"), Some(&1));
+        assert_eq!(table.label(&2), Some("y"));
+        assert_eq!(table.to_text(), "<eps>\t0\nx\t1\ny\t2\n");
+        assert!(matches!(
+            SymbolTable::<u32>::parse("x 1\ny two\n"),
+            Err(Error::MalformedLine { line: 2 })
+        ));
+
+        let table = SymbolTable::from_alphabet(&Range::NonEmpty {
+            start: ' ',
+            end: '!',
+        });
+        assert_eq!(table.label(&' '), Some("\\u{20}"));
+        assert_eq!(table.label(&'!'), Some("!"));
+        let table = SymbolTable::from_alphabet(&Range::NonEmpty {
+            start: '\\',
+            end: '\\',
+        });
+        assert_eq!(table.label(&'\\'), Some("\\\\"));
+    }
+
+    #[test]
+    fn symbol_table_text_with_whitespace_symbols() {
+        let mut table: SymbolTable<char> = SymbolTable::new();
+        table
+            .insert(' ', "space")
+            .insert('\t', "tab")
+            .insert('\\', "backslash");
+
+        let text = table.to_text();
+        assert_eq!(text, "backslash\t\\\\\nspace\t\\u{20}\ntab\t\\u{9}\n");
+
+        let parsed: SymbolTable<char> = SymbolTable::parse(&text).unwrap();
+        assert_eq!(parsed.symbol("space"), Some(&' '));
+        assert_eq!(parsed.symbol("tab"), Some(&'\t'));
+        assert_eq!(parsed.symbol("backslash"), Some(&'\\'));
+        assert_eq!(parsed.to_text(), text);
+
+        assert!(matches!(
+            SymbolTable::<char>::parse("x \\u{zz}\n"),
+            Err(Error::MalformedLine { line: 1 })
+        ));
+    }
+}
@@ -0,0 +1,601 @@
+use crate::{
+    alphabet::{Alphabet, Boolean, Unit},
+    collections::BTreeMap,
+    dfa::{DFAStorage, DFA},
+    error::Error,
+    util::{Range, Step, VecSet},
+};
+use alloc::{borrow::Cow, vec, vec::Vec};
+use core::convert::TryInto;
+
+const MAGIC: &[u8; 4] = b"RDFA";
+const VERSION: u16 = 1;
+const HEADER_LEN: usize = 28;
+// Marks a missing transition in the table, and a missing dead state in the
+// header.
+const NONE: u32 = u32::MAX;
+
+/// An alphabet that can be stored in the binary DFA format.
+///
+/// Each symbol of the alphabet is assigned a column of the dense transition
+/// table.
+///
+/// # Safety
+/// `BinaryDFAStorage::transition_unchecked` indexes the transition table
+/// without bounds checks, so implementations must guarantee that:
+/// 1. `symbol_index` returns `Some(idx)` with `idx < num_symbols` for every
+///    symbol in the alphabet.
+/// 2. The `values` of the alphabet are yielded in order of their index.
+pub unsafe trait BinaryAlphabet: Alphabet + Sized {
+    /// Return the number of symbols in this alphabet, or `None` if that
+    /// number does not fit in a `u32`.
+    fn num_symbols(&self) -> Option<usize>;
+
+    /// Return the column of the given symbol, or `None` if it is not a member
+    /// of this alphabet.
+    fn symbol_index(&self, sym: &Self::Symbol) -> Option<usize>;
+
+    /// Append a description of this alphabet to the output.
+    fn write_description(&self, out: &mut Vec<u8>);
+
+    /// Reconstruct an alphabet from its description, returning `None` if the
+    /// description is malformed.
+    fn read_description(description: &[u8]) -> Option<Self>;
+}
+
+unsafe impl BinaryAlphabet for Boolean {
+    fn num_symbols(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn symbol_index(&self, sym: &bool) -> Option<usize> {
+        Some(*sym as usize)
+    }
+
+    fn write_description(&self, _out: &mut Vec<u8>) {}
+
+    fn read_description(description: &[u8]) -> Option<Self> {
+        if description.is_empty() {
+            Some(Boolean)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl BinaryAlphabet for Unit {
+    fn num_symbols(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn symbol_index(&self, _sym: &()) -> Option<usize> {
+        Some(0)
+    }
+
+    fn write_description(&self, _out: &mut Vec<u8>) {}
+
+    fn read_description(description: &[u8]) -> Option<Self> {
+        if description.is_empty() {
+            Some(Unit)
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! range_binary_alphabet {
+    ($($sym:ty => $repr:ty),*) => {
+        $(
+            unsafe impl BinaryAlphabet for Range<$sym> {
+                fn num_symbols(&self) -> Option<usize> {
+                    let num_symbols = match self {
+                        Range::NonEmpty { start, end } => {
+                            Step::steps_between(start, end)?.checked_add(1)?
+                        }
+                        Range::Empty => 0,
+                    };
+
+                    if num_symbols < NONE as usize {
+                        Some(num_symbols)
+                    } else {
+                        None
+                    }
+                }
+
+                fn symbol_index(&self, sym: &$sym) -> Option<usize> {
+                    match self {
+                        Range::NonEmpty { start, end } if sym <= end => {
+                            Step::steps_between(start, sym)
+                        }
+                        _ => None,
+                    }
+                }
+
+                fn write_description(&self, out: &mut Vec<u8>) {
+                    if let Range::NonEmpty { start, end } = self {
+                        out.extend_from_slice(&<$repr>::from(*start).to_le_bytes());
+                        out.extend_from_slice(&<$repr>::from(*end).to_le_bytes());
+                    }
+                }
+
+                fn read_description(description: &[u8]) -> Option<Self> {
+                    const WIDTH: usize = core::mem::size_of::<$repr>();
+
+                    if description.is_empty() {
+                        return Some(Range::Empty);
+                    } else if description.len() != 2 * WIDTH {
+                        return None;
+                    }
+
+                    let start = <$repr>::from_le_bytes(description[..WIDTH].try_into().ok()?);
+                    let end = <$repr>::from_le_bytes(description[WIDTH..].try_into().ok()?);
+                    let start: $sym = start.try_into().ok()?;
+                    let end: $sym = end.try_into().ok()?;
+
+                    if start <= end {
+                        Some(Range::NonEmpty { start, end })
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    };
+}
+
+range_binary_alphabet!(u8 => u8, u16 => u16, u32 => u32, char => u32);
+
+/// Storage backed by the dense transition table of the binary DFA format.
+///
+/// A storage loaded with `DFA::from_binary` borrows the table from the input
+/// bytes, so loading does not copy it. The table is only copied if states or
+/// transitions are later added to the storage.
+#[derive(Debug, Clone)]
+pub struct BinaryDFAStorage<'a, A> {
+    alphabet: A,
+    num_symbols: usize,
+    num_states: usize,
+    table: Cow<'a, [u8]>,
+}
+
+impl<'a, A> BinaryDFAStorage<'a, A> {
+    fn entry(&self, state: usize, column: usize) -> u32 {
+        let offset = 4 * (state * self.num_symbols + column);
+
+        read_u32(&self.table, offset)
+    }
+}
+
+impl<'a, A> DFAStorage<A> for BinaryDFAStorage<'a, A>
+where
+    A: BinaryAlphabet,
+{
+    type State = usize;
+
+    /// Construct an empty storage for the given alphabet.
+    ///
+    /// # Panics
+    /// This function will panic if the alphabet has too many symbols to be
+    /// stored in the binary format.
+    fn from_alphabet(alphabet: A) -> Self {
+        let num_symbols = alphabet
+            .num_symbols()
+            .expect("alphabet is too large for the binary format");
+
+        BinaryDFAStorage {
+            alphabet,
+            num_symbols,
+            num_states: 0,
+            table: Cow::Owned(Vec::new()),
+        }
+    }
+
+    fn alphabet(&self) -> &A {
+        &self.alphabet
+    }
+
+    fn all_states(&self) -> Vec<Self::State> {
+        (0..self.num_states).collect()
+    }
+
+    fn all_transitions(&self) -> Vec<(Self::State, A::Symbol, Self::State)> {
+        let mut transitions = Vec::new();
+        for state in 0..self.num_states {
+            for (column, sym) in self.alphabet.values().enumerate() {
+                let next = self.entry(state, column);
+                if next != NONE {
+                    transitions.push((state, sym, next as usize));
+                }
+            }
+        }
+
+        transitions
+    }
+
+    fn contains_state(&self, state: &Self::State) -> bool {
+        *state < self.num_states
+    }
+
+    fn transition(&self, current: Self::State, sym: A::Symbol) -> Option<Self::State> {
+        if current >= self.num_states {
+            return None;
+        }
+
+        match self.entry(current, self.alphabet.symbol_index(&sym)?) {
+            NONE => None,
+            next => Some(next as usize),
+        }
+    }
+
+    #[inline]
+    unsafe fn transition_unchecked(&self, current: Self::State, sym: A::Symbol) -> Self::State {
+        let column = match self.alphabet.symbol_index(&sym) {
+            Some(column) => column,
+            None => core::hint::unreachable_unchecked(),
+        };
+        let offset = 4 * (current * self.num_symbols + column);
+        let bytes = self.table.get_unchecked(offset..offset + 4);
+
+        match u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) {
+            NONE => panic!("missing transition from state {}", current),
+            next => next as usize,
+        }
+    }
+
+    /// Return a new unique state.
+    ///
+    /// # Panics
+    /// This function will panic if the storage already has the maximum number
+    /// of states supported by the binary format.
+    fn add_state(&mut self) -> Self::State {
+        assert!(
+            self.num_states + 1 < NONE as usize,
+            "too many states for the binary format"
+        );
+
+        let new_state = self.num_states;
+        let row = NONE.to_le_bytes().repeat(self.num_symbols);
+        self.table.to_mut().extend_from_slice(&row);
+        self.num_states += 1;
+
+        new_state
+    }
+
+    fn add_transition(&mut self, from: Self::State, sym: A::Symbol, to: Self::State) {
+        let column = self
+            .alphabet
+            .symbol_index(&sym)
+            .expect("symbol is not in the alphabet");
+        let offset = 4 * (from * self.num_symbols + column);
+
+        self.table.to_mut()[offset..offset + 4].copy_from_slice(&(to as u32).to_le_bytes());
+    }
+
+    fn replace_transition(
+        &mut self,
+        from: Self::State,
+        sym: A::Symbol,
+        to: Self::State,
+    ) -> Option<(A::Symbol, Self::State)> {
+        let column = self
+            .alphabet
+            .symbol_index(&sym)
+            .expect("symbol is not in the alphabet");
+        let previous = self.entry(from, column);
+        let offset = 4 * (from * self.num_symbols + column);
+
+        self.table.to_mut()[offset..offset + 4].copy_from_slice(&(to as u32).to_le_bytes());
+
+        match previous {
+            NONE => None,
+            previous => Some((sym, previous as usize)),
+        }
+    }
+
+    fn remove_transition(
+        &mut self,
+        from: Self::State,
+        sym: A::Symbol,
+    ) -> Result<Option<Self::State>, Error> {
+        // Like the default storage, there is nothing to remove for an invalid
+        // state or symbol.
+        let column = match self.alphabet.symbol_index(&sym) {
+            Some(column) if from < self.num_states => column,
+            _ => return Ok(None),
+        };
+
+        // Only copy a borrowed table if it changes.
+        match self.entry(from, column) {
+            NONE => Ok(None),
+            previous => {
+                let offset = 4 * (from * self.num_symbols + column);
+                self.table.to_mut()[offset..offset + 4].copy_from_slice(&NONE.to_le_bytes());
+
+                Ok(Some(previous as usize))
+            }
+        }
+    }
+}
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: BinaryAlphabet,
+{
+    /// Encode this DFA in the binary format, which can be loaded with
+    /// `DFA::from_binary`.
+    ///
+    /// The format consists of a header, a description of the alphabet, a
+    /// dense transition table with one row per state, and a bitmap of the
+    /// accept states. All integers are little endian. State labels are not
+    /// stored.
+    ///
+    /// # Error
+    ///
+    /// This function will error if the DFA has too many states or symbols to
+    /// be represented in the binary format.
+    pub fn to_binary(&self) -> Result<Vec<u8>, Error> {
+        let states = self.storage.all_states();
+        let indices: BTreeMap<&S::State, u32> = states
+            .iter()
+            .enumerate()
+            .map(|(idx, state)| (state, idx as u32))
+            .collect();
+        let num_symbols = self
+            .alphabet()
+            .num_symbols()
+            .ok_or(Error::InvalidBinaryFormat {
+                reason: "too many symbols",
+            })?;
+        if states.len() >= NONE as usize {
+            return Err(Error::InvalidBinaryFormat {
+                reason: "too many states",
+            });
+        }
+
+        let mut description = Vec::new();
+        self.alphabet().write_description(&mut description);
+
+        let mut bytes = Vec::with_capacity(
+            HEADER_LEN + description.len() + 4 * states.len() * num_symbols + states.len() / 8 + 1,
+        );
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&(states.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(num_symbols as u32).to_le_bytes());
+        bytes.extend_from_slice(&indices[&self.start].to_le_bytes());
+        let dead = self.dead.as_ref().map_or(NONE, |dead| indices[dead]);
+        bytes.extend_from_slice(&dead.to_le_bytes());
+        bytes.extend_from_slice(&(description.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&description);
+
+        for state in &states {
+            for sym in self.alphabet().values() {
+                let next = self
+                    .storage
+                    .transition(state.clone(), sym)
+                    .map_or(NONE, |next| indices[&next]);
+                bytes.extend_from_slice(&next.to_le_bytes());
+            }
+        }
+
+        let mut bitmap = vec![0u8; states.len().div_ceil(8)];
+        for state in &self.accept {
+            let idx = indices[state] as usize;
+            bitmap[idx / 8] |= 1 << (idx % 8);
+        }
+        bytes.extend_from_slice(&bitmap);
+
+        Ok(bytes)
+    }
+}
+
+impl<'a, A> DFA<A, BinaryDFAStorage<'a, A>>
+where
+    A: BinaryAlphabet,
+{
+    /// Load a DFA from bytes produced by `DFA::to_binary`.
+    ///
+    /// The transition table is borrowed from the input, only the header and
+    /// the set of accept states are decoded into new memory. The loaded DFA
+    /// has no state labels, since the format does not store them.
+    ///
+    /// # Error
+    ///
+    /// This function will error if the bytes are not in a supported version of
+    /// the format, or if any part of them is inconsistent: the alphabet or the
+    /// section lengths do not match the header, or a state is out of range.
+    pub fn from_binary(bytes: &'a [u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(Error::InvalidBinaryFormat {
+                reason: "missing header",
+            });
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        let reserved = u16::from_le_bytes([bytes[6], bytes[7]]);
+        if version != VERSION || reserved != 0 {
+            return Err(Error::InvalidBinaryFormat {
+                reason: "unsupported version",
+            });
+        }
+
+        let num_states = read_u32(bytes, 8) as usize;
+        let num_symbols = read_u32(bytes, 12) as usize;
+        let start = read_u32(bytes, 16) as usize;
+        let dead = read_u32(bytes, 20);
+        let description_len = read_u32(bytes, 24) as usize;
+
+        let length_mismatch = Error::InvalidBinaryFormat {
+            reason: "length does not match header",
+        };
+        let table_start = HEADER_LEN
+            .checked_add(description_len)
+            .ok_or_else(|| length_mismatch.clone())?;
+        let table_end = num_states
+            .checked_mul(num_symbols)
+            .and_then(|len| len.checked_mul(4))
+            .and_then(|len| len.checked_add(table_start))
+            .ok_or_else(|| length_mismatch.clone())?;
+        let bitmap_len = num_states.div_ceil(8);
+        if table_end.checked_add(bitmap_len) != Some(bytes.len()) {
+            return Err(length_mismatch);
+        }
+
+        let invalid_alphabet = Error::InvalidBinaryFormat {
+            reason: "invalid alphabet",
+        };
+        let alphabet = A::read_description(&bytes[HEADER_LEN..table_start])
+            .ok_or_else(|| invalid_alphabet.clone())?;
+        if alphabet.num_symbols() != Some(num_symbols) || num_states >= NONE as usize {
+            return Err(invalid_alphabet);
+        }
+
+        // Every entry must be a valid state, so that the result of a
+        // transition can always be used as the next state.
+        let table = &bytes[table_start..table_end];
+        let in_range = table.chunks_exact(4).all(|entry| {
+            let next = u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]);
+            next == NONE || (next as usize) < num_states
+        });
+        if !in_range || start >= num_states || (dead != NONE && dead as usize >= num_states) {
+            return Err(Error::InvalidBinaryFormat {
+                reason: "state out of range",
+            });
+        }
+
+        let bitmap = &bytes[table_end..];
+        if !num_states.is_multiple_of(8) && bitmap[bitmap_len - 1] >> (num_states % 8) != 0 {
+            return Err(Error::InvalidBinaryFormat {
+                reason: "accept state out of range",
+            });
+        }
+        let accept: VecSet<usize> = (0..num_states)
+            .filter(|idx| bitmap[idx / 8] & (1 << (idx % 8)) != 0)
+            .collect();
+
+        Ok(DFA {
+            accept,
+            dead: if dead == NONE {
+                None
+            } else {
+                Some(dead as usize)
+            },
+            start,
+            storage: BinaryDFAStorage {
+                alphabet,
+                num_symbols,
+                num_states,
+                table: Cow::Borrowed(table),
+            },
+            labels: BTreeMap::new(),
+        })
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(
+        bytes[offset..offset + 4]
+            .try_into()
+            .expect("slice has length 4"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dfa::DFABuilder,
+        test_helper::{CONTAINS_EVEN_TRUES_DFA, CONTAINS_TWO_FALSE_DFA},
+    };
+
+    #[test]
+    fn round_trip() {
+        for original in &[&*CONTAINS_TWO_FALSE_DFA, &*CONTAINS_EVEN_TRUES_DFA] {
+            let bytes = original.to_binary().unwrap();
+            let loaded = DFA::<Boolean, BinaryDFAStorage<Boolean>>::from_binary(&bytes).unwrap();
+
+            assert_eq!(loaded.start_state(), original.start_state());
+            assert_eq!(loaded.accept_states(), original.accept_states());
+            assert_eq!(loaded.dead_state(), original.dead_state());
+            assert!(loaded.is_equivalent(*original).unwrap());
+            for string in &[vec![], vec![false, true, false], vec![true, true, false]] {
+                assert_eq!(
+                    unsafe { loaded.accept_unchecked(string.iter().copied()) },
+                    original.accept(string.iter().copied())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn build_with_binary_storage() {
+        let mut builder =
+            DFABuilder::new_with_storage(BinaryDFAStorage::from_alphabet(Range::NonEmpty {
+                start: 'a',
+                end: 'c',
+            }));
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        builder
+            .transitions(vec![(q0, 'a', q1), (q1, 'c', q0)])
+            .unwrap();
+        builder.start_state(q0).accept_states(vec![q1]);
+        let dfa = builder.build().unwrap();
+
+        assert!(dfa.accept("aca".chars()));
+        assert!(!dfa.accept("ab".chars()));
+
+        let bytes = dfa.to_binary().unwrap();
+        let loaded = DFA::<Range<char>, BinaryDFAStorage<_>>::from_binary(&bytes).unwrap();
+        assert!(loaded.accept("acaca".chars()));
+        assert_eq!(loaded.storage().all_transitions().len(), 2);
+
+        // Adding to a loaded storage copies the table instead of failing.
+        let mut builder = loaded.into_builder();
+        let q2 = builder.new_state();
+        builder.transition(q1, 'b', q2).unwrap();
+        builder.accept_states(vec![q2]);
+        assert_eq!(builder.remove_transition(q1, 'c').unwrap(), Some(q0));
+        assert!(matches!(
+            builder.remove_state(q2),
+            Err(Error::UnsupportedOperation { .. })
+        ));
+        let dfa = builder.build().unwrap();
+        assert!(dfa.accept("ab".chars()));
+        assert!(!dfa.accept("aca".chars()));
+    }
+
+    #[test]
+    fn rejects_malformed_bytes() {
+        let bytes = CONTAINS_TWO_FALSE_DFA.to_binary().unwrap();
+        let load = |bytes: &[u8]| {
+            DFA::<Boolean, BinaryDFAStorage<Boolean>>::from_binary(bytes)
+                .map(|_| ())
+                .map_err(|err| matches!(err, Error::InvalidBinaryFormat { .. }))
+        };
+        let corrupt = |offset: usize, value: u8| {
+            let mut corrupted = bytes.clone();
+            corrupted[offset] = value;
+            load(&corrupted)
+        };
+
+        assert_eq!(load(&bytes), Ok(()));
+        // Magic, version, truncation and trailing data.
+        assert_eq!(corrupt(0, b'X'), Err(true));
+        assert_eq!(corrupt(4, 2), Err(true));
+        assert_eq!(load(&bytes[..bytes.len() - 1]), Err(true));
+        assert_eq!(load(&[bytes.as_slice(), &[0]].concat()), Err(true));
+        // Start state, dead state and a transition out of range.
+        assert_eq!(corrupt(16, 3), Err(true));
+        assert_eq!(corrupt(20, 3), Err(true));
+        assert_eq!(corrupt(HEADER_LEN, 7), Err(true));
+        // Accept bit for a state that does not exist.
+        assert_eq!(corrupt(bytes.len() - 1, 0b1000), Err(true));
+        // Alphabet description that does not match the symbol count.
+        let mut wrong_symbols = bytes.clone();
+        wrong_symbols[12] = 3;
+        assert_eq!(load(&wrong_symbols), Err(true));
+    }
+}
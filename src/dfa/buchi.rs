@@ -0,0 +1,502 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::{BTreeMap, VecDeque},
+    dfa::{DFABuilder, DFAStorage, DFA},
+    error::Error,
+    util::VecSet,
+};
+use alloc::{vec, vec::Vec};
+
+/// A deterministic Büchi automaton, which accepts infinite strings.
+///
+/// The automaton shares its representation with the `DFA`: an infinite string
+/// is accepted if the run over it visits an accept state infinitely often. A
+/// missing transition rejects the string.
+#[derive(Debug, Clone)]
+pub struct BuchiAutomaton<
+    A: Alphabet,
+    S: DFAStorage<A> = super::DefaultDFAStorage<A, <A as Alphabet>::Symbol>,
+> {
+    accept: VecSet<S::State>,
+    start: S::State,
+    storage: S,
+}
+
+impl<A, S> From<DFA<A, S>> for BuchiAutomaton<A, S>
+where
+    S: DFAStorage<A>,
+    A: Alphabet,
+{
+    /// Reinterpret the accept states of a DFA as Büchi accept states.
+    ///
+    /// A DFA stops reading at its dead state, so the dead state becomes a sink
+    /// with a transition to itself on every symbol. An accepting dead state
+    /// then accepts every infinite string with a prefix that reaches it.
+    fn from(dfa: DFA<A, S>) -> Self {
+        let mut storage = dfa.storage;
+        if let Some(dead) = dfa.dead {
+            let symbols: Vec<A::Symbol> = storage.alphabet().values().collect();
+            for sym in symbols {
+                storage.add_transition(dead.clone(), sym, dead.clone());
+            }
+        }
+
+        BuchiAutomaton {
+            accept: dfa.accept,
+            start: dfa.start,
+            storage,
+        }
+    }
+}
+
+// Outgoing transitions of every state, the only view of the storage needed for
+// the graph searches below.
+type Successors<State, Symbol> = BTreeMap<State, Vec<(Symbol, State)>>;
+
+// An ultimately periodic string, as a prefix and a period repeated forever.
+type Lasso<Symbol> = (Vec<Symbol>, Vec<Symbol>);
+
+// A state of a product, as the state of each side, where `None` is a run that
+// hit a missing transition, and whose turn it is to accept.
+type ProductKey<L, R> = (Option<L>, Option<R>, bool);
+
+impl<A, S> BuchiAutomaton<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone,
+{
+    /// The states that must be visited infinitely often for a string to be
+    /// accepted.
+    pub fn accept_states(&self) -> &[S::State] {
+        self.accept.as_slice()
+    }
+
+    /// The starting state of the automaton.
+    pub fn start_state(&self) -> &S::State {
+        &self.start
+    }
+
+    /// Return a reference to the `DFAStorage` backing this automaton.
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Accept or reject the ultimately periodic string `prefix` followed by
+    /// `period` repeated forever.
+    ///
+    /// An empty `period` does not describe an infinite string, and is always
+    /// rejected.
+    pub fn accept_lasso(&self, prefix: &[A::Symbol], period: &[A::Symbol]) -> bool {
+        if period.is_empty() {
+            return false;
+        }
+
+        let mut current = match self.run(self.start.clone(), prefix, &mut |_| ()) {
+            Some(state) => state,
+            None => return false,
+        };
+
+        // Each entry is the state at the start of one pass over the period, and
+        // whether the pass visited an accept state. The run is deterministic, so
+        // once a starting state repeats the passes between repeat forever.
+        let mut passes: Vec<(S::State, bool)> = Vec::new();
+        loop {
+            if let Some(first) = passes.iter().position(|(state, _)| *state == current) {
+                return passes[first..].iter().any(|(_, visited)| *visited);
+            }
+
+            let mut visited_accept = false;
+            let next = self.run(current.clone(), period, &mut |state| {
+                visited_accept |= self.accept.contains(state);
+            });
+
+            passes.push((current, visited_accept));
+            current = match next {
+                Some(state) => state,
+                None => return false,
+            };
+        }
+    }
+
+    /// Return `true` if this automaton accepts no infinite string.
+    pub fn is_empty(&self) -> bool {
+        self.accepting_lasso().is_none()
+    }
+
+    /// Find an ultimately periodic string `(prefix, period)` accepted by this
+    /// automaton, if one exists.
+    ///
+    /// The string is found by searching for an accept state that is reachable
+    /// from the start state and lies on a cycle.
+    pub fn accepting_lasso(&self) -> Option<Lasso<A::Symbol>> {
+        let successors = self.successors();
+        let reachable = shortest_paths(&successors, self.start.clone());
+
+        for accept in &self.accept {
+            let prefix = match reachable.get(accept) {
+                Some(_) => path_to(&reachable, accept),
+                None => continue,
+            };
+
+            // Search for the shortest non-empty path from the accept state back to
+            // itself.
+            for (sym, next) in successors.get(accept).into_iter().flatten() {
+                let from_next = shortest_paths(&successors, next.clone());
+
+                if from_next.contains_key(accept) {
+                    let mut period = vec![sym.clone()];
+                    period.extend(path_to(&from_next, accept));
+
+                    return Some((prefix, period));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Construct a new Büchi automaton that accepts the intersection of the
+    /// languages of this automaton and another.
+    pub fn intersection<S2, S3>(
+        &self,
+        other: &BuchiAutomaton<A, S2>,
+    ) -> Result<BuchiAutomaton<A, S3>, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        S3: DFAStorage<A>,
+        S3::State: Ord,
+        A: PartialEq + Clone,
+    {
+        product(self, other, true)
+    }
+
+    /// Construct a new Büchi automaton that accepts the union of the languages
+    /// of this automaton and another.
+    pub fn union<S2, S3>(
+        &self,
+        other: &BuchiAutomaton<A, S2>,
+    ) -> Result<BuchiAutomaton<A, S3>, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        S3: DFAStorage<A>,
+        S3::State: Ord,
+        A: PartialEq + Clone,
+    {
+        product(self, other, false)
+    }
+
+    fn run(
+        &self,
+        mut current: S::State,
+        string: &[A::Symbol],
+        visit: &mut impl FnMut(&S::State),
+    ) -> Option<S::State> {
+        for sym in string {
+            if !self.storage.alphabet().contains(sym) {
+                return None;
+            }
+
+            current = self.storage.transition(current, sym.clone())?;
+            visit(&current);
+        }
+
+        Some(current)
+    }
+
+    fn successors(&self) -> Successors<S::State, A::Symbol> {
+        let mut successors: Successors<S::State, A::Symbol> = BTreeMap::new();
+
+        for (from, sym, to) in self.storage.all_transitions() {
+            successors.entry(from).or_default().push((sym, to));
+        }
+
+        successors
+    }
+}
+
+// Breadth first search from `start`, recording for every reachable state the
+// transition that first reached it.
+fn shortest_paths<State, Symbol>(
+    successors: &Successors<State, Symbol>,
+    start: State,
+) -> BTreeMap<State, Option<(State, Symbol)>>
+where
+    State: Ord + Clone,
+    Symbol: Clone,
+{
+    let mut parents = BTreeMap::new();
+    let mut queue = VecDeque::new();
+
+    parents.insert(start.clone(), None);
+    queue.push_back(start);
+
+    while let Some(state) = queue.pop_front() {
+        for (sym, next) in successors.get(&state).into_iter().flatten() {
+            if !parents.contains_key(next) {
+                parents.insert(next.clone(), Some((state.clone(), sym.clone())));
+                queue.push_back(next.clone());
+            }
+        }
+    }
+
+    parents
+}
+
+fn path_to<State, Symbol>(
+    parents: &BTreeMap<State, Option<(State, Symbol)>>,
+    end: &State,
+) -> Vec<Symbol>
+where
+    State: Ord,
+    Symbol: Clone,
+{
+    let mut path = Vec::new();
+    let mut current = end;
+
+    while let Some(Some((parent, sym))) = parents.get(current) {
+        path.push(sym.clone());
+        current = parent;
+    }
+
+    path.reverse();
+    path
+}
+
+// Explore the reachable product of two Büchi automata. When `track_turn` is
+// set, each product state also records which side is next required to visit
+// an accept state, giving the standard intersection construction; otherwise a
+// product state accepts when either side does, giving the union.
+//
+// A side is `None` once its run has hit a missing transition. The intersection
+// needs both runs, so it has no transition there, while the union keeps going
+// as long as either run is alive.
+fn product<SL, SR, SN, A>(
+    left: &BuchiAutomaton<A, SL>,
+    right: &BuchiAutomaton<A, SR>,
+    track_turn: bool,
+) -> Result<BuchiAutomaton<A, SN>, Error>
+where
+    SL: DFAStorage<A>,
+    SL::State: Ord,
+    SR: DFAStorage<A>,
+    SR::State: Ord,
+    SN: DFAStorage<A>,
+    SN::State: Ord,
+    A: Alphabet + PartialEq + Clone,
+    A::Symbol: Clone,
+{
+    if left.storage.alphabet() != right.storage.alphabet() {
+        return Err(Error::OperationWithNonEqualAlphabets {
+            operation: if track_turn { "intersection" } else { "union" },
+        });
+    }
+
+    let alphabet = left.storage.alphabet().clone();
+    let mut builder = DFABuilder::new_with_storage(SN::from_alphabet(alphabet));
+    let mut state_mapping: BTreeMap<ProductKey<SL::State, SR::State>, SN::State> = BTreeMap::new();
+    let mut queue = VecDeque::new();
+    let mut accept = Vec::new();
+
+    let start = (Some(left.start.clone()), Some(right.start.clone()), false);
+    let new_start = builder.new_state();
+    state_mapping.insert(start.clone(), new_start.clone());
+    queue.push_back(start);
+    builder.start_state(new_start);
+
+    while let Some((left_state, right_state, turn)) = queue.pop_front() {
+        let new_state = state_mapping[&(left_state.clone(), right_state.clone(), turn)].clone();
+        let left_accepts = left_state.as_ref().is_some_and(|s| left.accept.contains(s));
+        let right_accepts = right_state
+            .as_ref()
+            .is_some_and(|s| right.accept.contains(s));
+
+        let (accepts, next_turn) = if track_turn {
+            // `false` waits for the left side to accept, `true` for the right.
+            match turn {
+                false => (left_accepts, left_accepts),
+                true => (false, !right_accepts),
+            }
+        } else {
+            (left_accepts || right_accepts, false)
+        };
+
+        if accepts {
+            accept.push(new_state.clone());
+        }
+
+        for sym in builder.alphabet().values() {
+            let left_next = left_state
+                .clone()
+                .and_then(|s| left.storage.transition(s, sym.clone()));
+            let right_next = right_state
+                .clone()
+                .and_then(|s| right.storage.transition(s, sym.clone()));
+
+            let alive = if track_turn {
+                left_next.is_some() && right_next.is_some()
+            } else {
+                left_next.is_some() || right_next.is_some()
+            };
+
+            if alive {
+                let key = (left_next, right_next, next_turn);
+                let new_next = match state_mapping.get(&key) {
+                    Some(existing) => existing.clone(),
+                    None => {
+                        let created = builder.new_state();
+                        state_mapping.insert(key.clone(), created.clone());
+                        queue.push_back(key);
+                        created
+                    }
+                };
+
+                builder.add_transition(new_state.clone(), sym, new_next);
+            }
+        }
+    }
+
+    builder.accept_states(accept);
+
+    Ok(BuchiAutomaton::from(builder.build_unchecked()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alphabet::Boolean, dfa::DFABuilder};
+
+    // Infinitely many `true` symbols
+    fn infinitely_many_trues() -> BuchiAutomaton<Boolean> {
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+
+        builder
+            .transitions(vec![
+                (q0, false, q0),
+                (q0, true, q1),
+                (q1, false, q0),
+                (q1, true, q1),
+            ])
+            .unwrap();
+        builder.start_state(q0).accept_states(vec![q1]);
+
+        builder.build().unwrap().into()
+    }
+
+    // Infinitely many `false` symbols
+    fn infinitely_many_falses() -> BuchiAutomaton<Boolean> {
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+
+        builder
+            .transitions(vec![
+                (q0, false, q1),
+                (q0, true, q0),
+                (q1, false, q1),
+                (q1, true, q0),
+            ])
+            .unwrap();
+        builder.start_state(q0).accept_states(vec![q1]);
+
+        builder.build().unwrap().into()
+    }
+
+    #[test]
+    fn dead_state_is_a_sink() {
+        // Contains a `false`, where the accepting dead state has no transitions.
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let dead = builder.new_state();
+        builder
+            .transitions(vec![(q0, false, dead), (q0, true, q0)])
+            .unwrap();
+        builder
+            .start_state(q0)
+            .dead_state(Some(dead))
+            .accept_states(vec![dead]);
+        let automaton: BuchiAutomaton<Boolean> = builder.build().unwrap().into();
+
+        assert!(automaton.accept_lasso(&[false], &[true]));
+        assert!(automaton.accept_lasso(&[true], &[true, false]));
+        assert!(!automaton.accept_lasso(&[], &[true]));
+        assert!(!automaton.is_empty());
+    }
+
+    #[test]
+    fn accept_ultimately_periodic() {
+        let trues = infinitely_many_trues();
+
+        assert!(trues.accept_lasso(&[false, false], &[true]));
+        assert!(trues.accept_lasso(&[], &[false, true]));
+        assert!(!trues.accept_lasso(&[true, true], &[false]));
+        assert!(!trues.accept_lasso(&[true], &[]));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let trues = infinitely_many_trues();
+        let falses = infinitely_many_falses();
+
+        let both: BuchiAutomaton<Boolean> = trues.intersection(&falses).unwrap();
+        assert!(both.accept_lasso(&[], &[true, false]));
+        assert!(!both.accept_lasso(&[false], &[true]));
+        assert!(!both.accept_lasso(&[true], &[false]));
+
+        let either: BuchiAutomaton<Boolean> = trues.union(&falses).unwrap();
+        assert!(either.accept_lasso(&[false], &[true]));
+        assert!(either.accept_lasso(&[true], &[false]));
+        assert!(either.accept_lasso(&[], &[true, false]));
+    }
+
+    // Only the string `sym` repeated forever, with no transition on `!sym`.
+    fn only(sym: bool) -> BuchiAutomaton<Boolean> {
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+
+        builder.transitions(vec![(q0, sym, q0)]).unwrap();
+        builder.start_state(q0).accept_states(vec![q0]);
+
+        builder.build().unwrap().into()
+    }
+
+    #[test]
+    fn union_of_partial_automata() {
+        let trues = only(true);
+        let falses = only(false);
+
+        let either: BuchiAutomaton<Boolean> = trues.union(&falses).unwrap();
+        assert!(either.accept_lasso(&[], &[true]));
+        assert!(either.accept_lasso(&[], &[false]));
+        assert!(!either.accept_lasso(&[true], &[false, true]));
+        assert!(!either.is_empty());
+
+        let both: BuchiAutomaton<Boolean> = trues.intersection(&falses).unwrap();
+        assert!(both.is_empty());
+    }
+
+    #[test]
+    fn emptiness_finds_lasso() {
+        let trues = infinitely_many_trues();
+        let (prefix, period) = trues.accepting_lasso().unwrap();
+        assert!(trues.accept_lasso(&prefix, &period));
+
+        // An accept state that is reachable but not on a cycle
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        let q2 = builder.new_state();
+        builder
+            .transitions(vec![(q0, true, q1), (q1, true, q2), (q2, true, q2)])
+            .unwrap();
+        builder.start_state(q0).accept_states(vec![q1]);
+        let transient: BuchiAutomaton<Boolean> = builder.build().unwrap().into();
+
+        assert!(transient.is_empty());
+    }
+}
@@ -0,0 +1,583 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::BTreeMap,
+    dfa::{DFAStorage, DFA},
+};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+
+/// A symbol that can be written as a pattern in generated Rust code.
+pub trait RustSymbol {
+    /// The name of the symbol type in generated code.
+    fn type_name() -> &'static str;
+
+    /// Append a literal pattern matching this symbol to the output.
+    fn write_literal(&self, out: &mut String);
+
+    /// Return `true` if `next` immediately follows this symbol, with no other
+    /// values of the type between them. Runs of such symbols are written as a
+    /// single range pattern.
+    fn is_followed_by(&self, _next: &Self) -> bool {
+        false
+    }
+}
+
+impl RustSymbol for bool {
+    fn type_name() -> &'static str {
+        "bool"
+    }
+
+    fn write_literal(&self, out: &mut String) {
+        write!(out, "{}", self).unwrap();
+    }
+}
+
+impl RustSymbol for () {
+    fn type_name() -> &'static str {
+        "()"
+    }
+
+    fn write_literal(&self, out: &mut String) {
+        out.push_str("()");
+    }
+}
+
+impl RustSymbol for char {
+    fn type_name() -> &'static str {
+        "char"
+    }
+
+    fn write_literal(&self, out: &mut String) {
+        write!(out, "{:?}", self).unwrap();
+    }
+
+    fn is_followed_by(&self, next: &Self) -> bool {
+        // The surrogate gap cannot hold a `char`, so a range may span it.
+        (*self as u32 + 1 == *next as u32) || (*self == '\u{D7FF}' && *next == '\u{E000}')
+    }
+}
+
+macro_rules! integer_rust_symbol {
+    ($($int:ty),*) => {
+        $(
+            impl RustSymbol for $int {
+                fn type_name() -> &'static str {
+                    stringify!($int)
+                }
+
+                fn write_literal(&self, out: &mut String) {
+                    write!(out, "{}", self).unwrap();
+                }
+
+                fn is_followed_by(&self, next: &Self) -> bool {
+                    self.checked_add(1) == Some(*next)
+                }
+            }
+        )*
+    };
+}
+
+integer_rust_symbol!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// The shape of the code generated by `DFA::to_rust_source`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CodegenStyle {
+    /// A `match` on the current state, containing a `match` on the symbol.
+    Match,
+    /// A static transition table, indexed by the current state and by the
+    /// class of the symbol. Symbols that behave the same in every state share
+    /// a class, and so a column of the table.
+    Table,
+}
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone + RustSymbol,
+{
+    /// Generate the source of a standalone Rust function that accepts the
+    /// same strings as this DFA, for example to be written to a file by a
+    /// build script.
+    ///
+    /// The generated function has the signature
+    /// `pub fn name<I: IntoIterator<Item = Symbol>>(string: I) -> bool` and
+    /// does not depend on this crate.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `fn_name` is not a valid Rust identifier,
+    /// such as a keyword or a name containing spaces or punctuation.
+    pub fn to_rust_source(&self, fn_name: &str, style: CodegenStyle) -> String {
+        assert!(
+            is_identifier(fn_name),
+            "{:?} is not a valid Rust identifier",
+            fn_name
+        );
+
+        let states = self.storage.all_states();
+        let symbols: Vec<A::Symbol> = self.alphabet().values().collect();
+        let indices: BTreeMap<&S::State, usize> = states
+            .iter()
+            .enumerate()
+            .map(|(idx, state)| (state, idx))
+            .collect();
+        let index = |state: &S::State| indices[state];
+        // The target of every transition, by state and then by symbol.
+        let next: Vec<Vec<Option<usize>>> = states
+            .iter()
+            .map(|state| {
+                symbols
+                    .iter()
+                    .map(|sym| {
+                        self.storage
+                            .transition(state.clone(), sym.clone())
+                            .map(|target| index(&target))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            "/// Generated by `regular` from a DFA with {} states.",
+            states.len()
+        )
+        .unwrap();
+        // Patterns for a complete alphabet are followed by a catch all arm, and
+        // a DFA without transitions never looks at the symbol.
+        writeln!(out, "#[allow(unreachable_patterns, unused_variables)]").unwrap();
+        writeln!(
+            out,
+            "pub fn {}<I: IntoIterator<Item = {}>>(string: I) -> bool {{",
+            fn_name,
+            A::Symbol::type_name()
+        )
+        .unwrap();
+
+        let accept: Vec<String> = states
+            .iter()
+            .map(|state| self.accept.contains(state).to_string())
+            .collect();
+        writeln!(
+            out,
+            "    const ACCEPT: [bool; {}] = [{}];",
+            states.len(),
+            accept.join(", ")
+        )
+        .unwrap();
+
+        match style {
+            CodegenStyle::Match => write_match_body(&mut out, &symbols, &next),
+            CodegenStyle::Table => write_table_body(&mut out, &symbols, &next),
+        }
+
+        writeln!(out, "    let mut state: usize = {};", index(&self.start)).unwrap();
+        writeln!(out, "    for sym in string {{").unwrap();
+        // Like `DFA::accept`, stop reading before a symbol once the dead state
+        // was reached.
+        if let Some(dead) = &self.dead {
+            writeln!(out, "        if state == {} {{", index(dead)).unwrap();
+            writeln!(out, "            break;").unwrap();
+            writeln!(out, "        }}").unwrap();
+        }
+        match style {
+            CodegenStyle::Match => {
+                writeln!(out, "        state = match step(state, sym) {{").unwrap();
+                writeln!(out, "            Some(next) => next,").unwrap();
+                writeln!(out, "            None => return false,").unwrap();
+                writeln!(out, "        }};").unwrap();
+            }
+            CodegenStyle::Table => {
+                writeln!(out, "        let class = match class(sym) {{").unwrap();
+                writeln!(out, "            Some(class) => class,").unwrap();
+                writeln!(out, "            None => return false,").unwrap();
+                writeln!(out, "        }};").unwrap();
+                writeln!(out, "        state = match TABLE[state][class] {{").unwrap();
+                writeln!(out, "            NONE => return false,").unwrap();
+                writeln!(out, "            next => next as usize,").unwrap();
+                writeln!(out, "        }};").unwrap();
+            }
+        }
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "    ACCEPT[state]").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        out
+    }
+}
+
+// A nested function `step`, which returns the next state or `None` if there
+// is no transition.
+fn write_match_body<Sym: RustSymbol>(
+    out: &mut String,
+    symbols: &[Sym],
+    next: &[Vec<Option<usize>>],
+) {
+    writeln!(
+        out,
+        "    fn step(state: usize, sym: {}) -> Option<usize> {{",
+        Sym::type_name()
+    )
+    .unwrap();
+    if next.iter().flatten().all(Option::is_none) {
+        writeln!(out, "        None").unwrap();
+        writeln!(out, "    }}").unwrap();
+        return;
+    }
+    writeln!(out, "        Some(match state {{").unwrap();
+
+    for (state, row) in next.iter().enumerate() {
+        if row.iter().all(Option::is_none) {
+            continue;
+        }
+
+        writeln!(out, "            {} => match sym {{", state).unwrap();
+        for (target, members) in group_by_value(row) {
+            let target = match target {
+                Some(target) => target,
+                None => continue,
+            };
+            writeln!(
+                out,
+                "                {} => {},",
+                pattern(symbols, &members),
+                target
+            )
+            .unwrap();
+        }
+        writeln!(out, "                _ => return None,").unwrap();
+        writeln!(out, "            }},").unwrap();
+    }
+
+    writeln!(out, "            _ => return None,").unwrap();
+    writeln!(out, "        }})").unwrap();
+    writeln!(out, "    }}").unwrap();
+}
+
+// A nested function `class`, which returns the column of a symbol in the
+// static `TABLE`, or `None` if the symbol has no transitions at all.
+fn write_table_body<Sym: RustSymbol>(
+    out: &mut String,
+    symbols: &[Sym],
+    next: &[Vec<Option<usize>>],
+) {
+    let columns: Vec<Vec<Option<usize>>> = (0..symbols.len())
+        .map(|sym_idx| next.iter().map(|row| row[sym_idx]).collect())
+        .collect();
+    let classes: Vec<(Vec<Option<usize>>, Vec<usize>)> = group_by_value(&columns)
+        .into_iter()
+        .filter(|(column, _)| column.iter().any(Option::is_some))
+        .collect();
+
+    // The smallest integer type that holds every state and a marker for
+    // missing transitions.
+    let entry_type = if next.len() < u8::MAX as usize {
+        "u8"
+    } else if next.len() < u16::MAX as usize {
+        "u16"
+    } else {
+        "u32"
+    };
+
+    writeln!(out, "    const NONE: {} = {}::MAX;", entry_type, entry_type).unwrap();
+    writeln!(
+        out,
+        "    static TABLE: [[{}; {}]; {}] = [",
+        entry_type,
+        classes.len(),
+        next.len()
+    )
+    .unwrap();
+    for state in 0..next.len() {
+        let row: Vec<String> = classes
+            .iter()
+            .map(|(column, _)| match column[state] {
+                Some(target) => target.to_string(),
+                None => "NONE".to_string(),
+            })
+            .collect();
+        writeln!(out, "        [{}],", row.join(", ")).unwrap();
+    }
+    writeln!(out, "    ];").unwrap();
+
+    writeln!(
+        out,
+        "    fn class(sym: {}) -> Option<usize> {{",
+        Sym::type_name()
+    )
+    .unwrap();
+    if classes.is_empty() {
+        writeln!(out, "        None").unwrap();
+        writeln!(out, "    }}").unwrap();
+        return;
+    }
+    writeln!(out, "        Some(match sym {{").unwrap();
+    for (class, (_, members)) in classes.iter().enumerate() {
+        writeln!(
+            out,
+            "            {} => {},",
+            pattern(symbols, members),
+            class
+        )
+        .unwrap();
+    }
+    writeln!(out, "            _ => return None,").unwrap();
+    writeln!(out, "        }})").unwrap();
+    writeln!(out, "    }}").unwrap();
+}
+
+// Group the indices of the given values by value, in order of first
+// appearance.
+fn group_by_value<T: Clone + PartialEq>(values: &[T]) -> Vec<(T, Vec<usize>)> {
+    let mut groups: Vec<(T, Vec<usize>)> = Vec::new();
+
+    for (idx, value) in values.iter().enumerate() {
+        match groups.iter_mut().find(|(other, _)| other == value) {
+            Some((_, members)) => members.push(idx),
+            None => groups.push((value.clone(), vec![idx])),
+        }
+    }
+
+    groups
+}
+
+// A pattern matching exactly the symbols at the given sorted indices, with
+// runs of adjacent symbols written as ranges.
+fn pattern<Sym: RustSymbol>(symbols: &[Sym], members: &[usize]) -> String {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for &idx in members {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == idx && symbols[*last].is_followed_by(&symbols[idx]) => {
+                *last = idx
+            }
+            _ => runs.push((idx, idx)),
+        }
+    }
+
+    let mut pattern = String::new();
+    for (run_idx, (first, last)) in runs.into_iter().enumerate() {
+        if run_idx > 0 {
+            pattern.push_str(" | ");
+        }
+
+        symbols[first].write_literal(&mut pattern);
+        if first != last {
+            pattern.push_str("..=");
+            symbols[last].write_literal(&mut pattern);
+        }
+    }
+
+    pattern
+}
+
+// Keywords that cannot be used as a plain identifier in any edition.
+const KEYWORDS: &[&str] = &[
+    "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+// Return `true` if the name can be used as the name of a function. Only the
+// alphanumeric subset of non-ASCII identifiers is accepted.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_well = match chars.next() {
+        Some(first) => first == '_' || first.is_alphabetic(),
+        None => false,
+    };
+
+    starts_well && chars.all(|c| c == '_' || c.is_alphanumeric()) && !KEYWORDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet::Boolean, dfa::DFABuilder, test_helper::CONTAINS_TWO_FALSE_DFA, util::Range,
+    };
+
+    // The generated fixtures, compiled as part of the tests.
+    mod generated {
+        include!("../../tests/fixtures/codegen/two_false_match.rs");
+        include!("../../tests/fixtures/codegen/two_false_table.rs");
+        include!("../../tests/fixtures/codegen/identifier_match.rs");
+        include!("../../tests/fixtures/codegen/identifier_table.rs");
+        include!("../../tests/fixtures/codegen/dead_start_match.rs");
+        include!("../../tests/fixtures/codegen/dead_start_table.rs");
+    }
+
+    // Compare generated code to a fixture in `tests/fixtures/codegen`, which
+    // the `generated` module compiles. Outdated fixtures are only rewritten
+    // when the `UPDATE_FIXTURES` environment variable is set.
+    fn check_fixture(name: &str, source: String) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/codegen")
+            .join(name);
+
+        if std::env::var_os("UPDATE_FIXTURES").is_some() {
+            std::fs::write(&path, source).unwrap();
+        } else {
+            let fixture = std::fs::read_to_string(&path).unwrap_or_default();
+            assert!(
+                fixture == source,
+                "fixture {} is outdated, run the tests with UPDATE_FIXTURES=1 to rewrite it",
+                name
+            );
+        }
+    }
+
+    // Accepts identifiers made of lowercase letters and digits, that do not
+    // start with a digit.
+    fn identifier_dfa() -> DFA<Range<char>> {
+        let mut builder = DFABuilder::new('0'..='z');
+        let start = builder.new_state();
+        let rest = builder.new_state();
+        for sym in ('a'..='z').chain('0'..='9') {
+            if sym.is_ascii_lowercase() {
+                builder.transition(start, sym, rest).unwrap();
+            }
+            builder.transition(rest, sym, rest).unwrap();
+        }
+        builder.start_state(start).accept_states(vec![rest]);
+
+        builder.build().unwrap()
+    }
+
+    // Accepts every string, as the start state is an accepting dead state
+    // without transitions.
+    fn dead_start_dfa() -> DFA<Boolean> {
+        let mut builder = DFABuilder::new(Boolean);
+        let dead = builder.new_state();
+        builder
+            .start_state(dead)
+            .dead_state(Some(dead))
+            .accept_states(vec![dead]);
+
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn match_style() {
+        let source = CONTAINS_TWO_FALSE_DFA.to_rust_source("two_false", CodegenStyle::Match);
+
+        assert!(
+            source.contains("pub fn two_false<I: IntoIterator<Item = bool>>(string: I) -> bool {")
+        );
+        assert!(source.contains("const ACCEPT: [bool; 3] = [false, false, true];"));
+        assert!(source.contains(
+            "            0 => match sym {\n                false => 1,\n                true => \
+             0,\n"
+        ));
+        assert!(
+            source.contains("            2 => match sym {\n                false | true => 2,\n")
+        );
+        assert!(source.contains("        if state == 2 {\n            break;\n        }\n"));
+
+        let source = identifier_dfa().to_rust_source("identifier", CodegenStyle::Match);
+        assert!(
+            source.contains("                'a'..='z' => 1,\n                _ => return None,")
+        );
+        assert!(source.contains("                '0'..='9' | 'a'..='z' => 1,\n"));
+    }
+
+    #[test]
+    fn table_style() {
+        let source = identifier_dfa().to_rust_source("identifier", CodegenStyle::Table);
+
+        // Digits and letters are the only classes of symbols.
+        assert!(source.contains(
+            "    static TABLE: [[u8; 2]; 2] = [\n        [NONE, 1],\n        [1, 1],\n    ];"
+        ));
+        assert!(source.contains(
+            "            '0'..='9' => 0,\n            'a'..='z' => 1,\n            _ => return \
+             None,"
+        ));
+        assert!(!source.contains("break"));
+    }
+
+    #[test]
+    fn fixtures_are_current() {
+        check_fixture(
+            "two_false_match.rs",
+            CONTAINS_TWO_FALSE_DFA.to_rust_source("two_false_match", CodegenStyle::Match),
+        );
+        check_fixture(
+            "two_false_table.rs",
+            CONTAINS_TWO_FALSE_DFA.to_rust_source("two_false_table", CodegenStyle::Table),
+        );
+        check_fixture(
+            "identifier_match.rs",
+            identifier_dfa().to_rust_source("identifier_match", CodegenStyle::Match),
+        );
+        check_fixture(
+            "identifier_table.rs",
+            identifier_dfa().to_rust_source("identifier_table", CodegenStyle::Table),
+        );
+        check_fixture(
+            "dead_start_match.rs",
+            dead_start_dfa().to_rust_source("dead_start_match", CodegenStyle::Match),
+        );
+        check_fixture(
+            "dead_start_table.rs",
+            dead_start_dfa().to_rust_source("dead_start_table", CodegenStyle::Table),
+        );
+    }
+
+    #[test]
+    fn generated_code_runs() {
+        use crate::test_helper::{binary_converter, convert_string};
+        use proptest::{prop_assert, prop_assert_eq, proptest};
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+            let expected = CONTAINS_TWO_FALSE_DFA.accept(string.iter().copied());
+
+            prop_assert_eq!(generated::two_false_match(string.iter().copied()), expected);
+            prop_assert_eq!(generated::two_false_table(string), expected);
+        });
+
+        let identifier = identifier_dfa();
+        proptest!(|(string in "[0-9a-z_A]*")| {
+            let expected = identifier.accept(string.chars());
+
+            prop_assert_eq!(generated::identifier_match(string.chars()), expected);
+            prop_assert_eq!(generated::identifier_table(string.chars()), expected);
+        });
+
+        let dead_start = dead_start_dfa();
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+
+            prop_assert!(dead_start.accept(string.iter().copied()));
+            prop_assert!(generated::dead_start_match(string.iter().copied()));
+            prop_assert!(generated::dead_start_table(string));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "not a valid Rust identifier")]
+    fn rejects_invalid_fn_name() {
+        CONTAINS_TWO_FALSE_DFA.to_rust_source("two false", CodegenStyle::Match);
+    }
+
+    #[test]
+    fn identifiers() {
+        assert!(is_identifier("two_false"));
+        assert!(is_identifier("_private"));
+        assert!(is_identifier("état2"));
+        assert!(!is_identifier(""));
+        assert!(!is_identifier("_"));
+        assert!(!is_identifier("2x"));
+        assert!(!is_identifier("match"));
+        assert!(!is_identifier("a-b"));
+    }
+}
@@ -0,0 +1,401 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::{BTreeMap, VecDeque},
+    dfa::{DFAStorage, DFA},
+    util::VecSet,
+};
+use alloc::{vec, vec::Vec};
+
+// The reachable part of a DFA, with states renumbered in breadth first order
+// so that state `0` is the start state. A missing transition leads to an
+// explicit rejecting sink state.
+struct Explored<Sym> {
+    symbols: Vec<Sym>,
+    access: Vec<Vec<Sym>>,
+    accepting: Vec<bool>,
+    next: Vec<Vec<usize>>,
+}
+
+impl<Sym> Explored<Sym>
+where
+    Sym: Clone + Ord,
+{
+    fn run(&self, string: &[Sym]) -> usize {
+        string.iter().fold(0, |state, sym| {
+            let sym_idx = self
+                .symbols
+                .binary_search(sym)
+                .expect("strings are built from the alphabet");
+            self.next[state][sym_idx]
+        })
+    }
+
+    // For every pair of states, a shortest suffix accepted from exactly one of
+    // them, computed by table filling. `None` means the states are equivalent.
+    fn separating_suffixes(&self) -> Vec<Vec<Option<Vec<Sym>>>> {
+        let num_states = self.accepting.len();
+        let mut separating: Vec<Vec<Option<Vec<Sym>>>> = self
+            .accepting
+            .iter()
+            .map(|p_accepts| {
+                self.accepting
+                    .iter()
+                    .map(|q_accepts| {
+                        if p_accepts != q_accepts {
+                            Some(Vec::new())
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Each round only reads the suffixes found in previous rounds, which
+        // are shorter than the round, so the suffixes found in round `i` have
+        // length `i`.
+        for round in 1.. {
+            let mut changed = false;
+
+            for p in 0..num_states {
+                for q in 0..num_states {
+                    if separating[p][q].is_some() {
+                        continue;
+                    }
+
+                    let found = self.symbols.iter().enumerate().find_map(|(idx, sym)| {
+                        separating[self.next[p][idx]][self.next[q][idx]]
+                            .as_ref()
+                            .filter(|suffix| suffix.len() < round)
+                            .map(|suffix| {
+                                let mut extended = vec![sym.clone()];
+                                extended.extend(suffix.iter().cloned());
+                                extended
+                            })
+                    });
+
+                    if found.is_some() {
+                        separating[p][q] = found;
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        separating
+    }
+}
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone + Ord,
+{
+    fn explore(&self) -> Explored<A::Symbol> {
+        let symbols: Vec<A::Symbol> = self
+            .storage
+            .alphabet()
+            .values()
+            .collect::<VecSet<_>>()
+            .into_iter()
+            .collect();
+        let mut explored = Explored {
+            symbols,
+            access: Vec::new(),
+            accepting: Vec::new(),
+            next: Vec::new(),
+        };
+        let mut states: Vec<Option<S::State>> = Vec::new();
+        let mut indices: BTreeMap<Option<S::State>, usize> = BTreeMap::new();
+        let mut queue = VecDeque::new();
+
+        let start = Some(self.start.clone());
+        indices.insert(start.clone(), 0);
+        states.push(start);
+        explored.access.push(Vec::new());
+        queue.push_back(0);
+
+        while let Some(idx) = queue.pop_front() {
+            let state = states[idx].clone();
+            explored
+                .accepting
+                .push(state.as_ref().is_some_and(|s| self.accept.contains(s)));
+
+            let mut next = Vec::with_capacity(explored.symbols.len());
+            for sym in &explored.symbols {
+                let target = state
+                    .clone()
+                    .and_then(|s| self.storage.transition(s, sym.clone()));
+                let target_idx = match indices.get(&target) {
+                    Some(target_idx) => *target_idx,
+                    None => {
+                        let target_idx = states.len();
+                        let mut access = explored.access[idx].clone();
+                        access.push(sym.clone());
+
+                        indices.insert(target.clone(), target_idx);
+                        states.push(target);
+                        explored.access.push(access);
+                        queue.push_back(target_idx);
+                        target_idx
+                    }
+                };
+
+                next.push(target_idx);
+            }
+            explored.next.push(next);
+        }
+
+        explored
+    }
+
+    /// Return a shortest access string for every state reachable from the
+    /// start state.
+    ///
+    /// If the DFA has missing transitions, the implicit rejecting state they
+    /// lead to is also covered.
+    pub fn state_cover(&self) -> Vec<Vec<A::Symbol>> {
+        self.explore().access
+    }
+
+    /// Return the state cover, extended with every string that exercises one
+    /// more transition from a covered state.
+    pub fn transition_cover(&self) -> Vec<Vec<A::Symbol>> {
+        transition_cover(&self.explore()).into_iter().collect()
+    }
+
+    /// Return a set of suffixes that distinguishes every pair of reachable,
+    /// inequivalent states: for each such pair, exactly one of the states
+    /// accepts one of the suffixes. The empty string is always included.
+    pub fn characterization_set(&self) -> Vec<Vec<A::Symbol>> {
+        characterization_set(&self.explore().separating_suffixes())
+            .into_iter()
+            .collect()
+    }
+
+    /// Generate a conformance test suite with the W-method.
+    ///
+    /// Any implementation with at most `extra_states` more states than the
+    /// minimal DFA of this language, that agrees with this DFA on every string
+    /// in the suite, accepts the same language. The minimal DFA is complete,
+    /// so it has a rejecting sink state whenever this DFA has missing
+    /// transitions.
+    pub fn w_method(&self, extra_states: usize) -> Vec<Vec<A::Symbol>> {
+        let explored = self.explore();
+        let characterization = characterization_set(&explored.separating_suffixes());
+        let middle = strings_up_to(&explored.symbols, extra_states);
+
+        concatenate(
+            &concatenate(&transition_cover(&explored), &middle),
+            &characterization,
+        )
+        .into_iter()
+        .collect()
+    }
+
+    /// Generate a conformance test suite with the Wp-method.
+    ///
+    /// The suite gives the same guarantee as the `w_method`, but is usually
+    /// smaller: only strings that reach states through the state cover are
+    /// followed by the full characterization set, other strings are followed
+    /// by just the suffixes that identify the state they reach.
+    pub fn wp_method(&self, extra_states: usize) -> Vec<Vec<A::Symbol>> {
+        let explored = self.explore();
+        let separating = explored.separating_suffixes();
+        let characterization = characterization_set(&separating);
+        let middle = strings_up_to(&explored.symbols, extra_states);
+        let state_cover: VecSet<Vec<A::Symbol>> = explored.access.iter().cloned().collect();
+
+        let mut suite = concatenate(&concatenate(&state_cover, &middle), &characterization);
+
+        let remaining: VecSet<_> = transition_cover(&explored)
+            .difference(&state_cover)
+            .cloned()
+            .collect();
+        for prefix in concatenate(&remaining, &middle) {
+            let reached = explored.run(&prefix);
+
+            for suffix in identification_set(&separating, reached) {
+                let mut test = prefix.clone();
+                test.extend(suffix);
+                suite.insert(test);
+            }
+        }
+
+        suite.into_iter().collect()
+    }
+}
+
+fn transition_cover<Sym: Clone + Ord>(explored: &Explored<Sym>) -> VecSet<Vec<Sym>> {
+    let mut cover: VecSet<Vec<Sym>> = explored.access.iter().cloned().collect();
+
+    for access in &explored.access {
+        for sym in &explored.symbols {
+            let mut extended = access.clone();
+            extended.push(sym.clone());
+            cover.insert(extended);
+        }
+    }
+
+    cover
+}
+
+fn characterization_set<Sym: Clone + Ord>(
+    separating: &[Vec<Option<Vec<Sym>>>],
+) -> VecSet<Vec<Sym>> {
+    let mut characterization: VecSet<Vec<Sym>> = separating
+        .iter()
+        .flatten()
+        .filter_map(|suffix| suffix.clone())
+        .collect();
+    characterization.insert(Vec::new());
+
+    characterization
+}
+
+// The suffixes that distinguish `state` from every other inequivalent state.
+fn identification_set<Sym: Clone + Ord>(
+    separating: &[Vec<Option<Vec<Sym>>>],
+    state: usize,
+) -> VecSet<Vec<Sym>> {
+    let mut identification: VecSet<Vec<Sym>> =
+        separating[state].iter().filter_map(|s| s.clone()).collect();
+    identification.insert(Vec::new());
+
+    identification
+}
+
+fn strings_up_to<Sym: Clone + Ord>(symbols: &[Sym], max_len: usize) -> VecSet<Vec<Sym>> {
+    let mut strings: VecSet<Vec<Sym>> = VecSet::new();
+    let mut frontier = vec![Vec::new()];
+
+    for _ in 0..max_len {
+        let mut next_frontier = Vec::with_capacity(frontier.len() * symbols.len());
+        for string in &frontier {
+            for sym in symbols {
+                let mut extended: Vec<Sym> = string.clone();
+                extended.push(sym.clone());
+                next_frontier.push(extended);
+            }
+        }
+
+        strings.extend(frontier);
+        frontier = next_frontier;
+    }
+    strings.extend(frontier);
+
+    strings
+}
+
+fn concatenate<Sym: Clone + Ord>(
+    prefixes: &VecSet<Vec<Sym>>,
+    suffixes: &VecSet<Vec<Sym>>,
+) -> VecSet<Vec<Sym>> {
+    let mut result = VecSet::with_capacity(prefixes.as_slice().len() * suffixes.as_slice().len());
+
+    for prefix in prefixes {
+        for suffix in suffixes {
+            let mut combined = prefix.clone();
+            combined.extend(suffix.iter().cloned());
+            result.insert(combined);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        alphabet::Boolean,
+        dfa::{DFABuilder, DFA},
+        test_helper::CONTAINS_TWO_FALSE_DFA,
+    };
+
+    // CONTAINS_TWO_FALSE_DFA with the given transitions swapped in.
+    fn mutant(changes: &[(usize, bool, usize)]) -> DFA<Boolean> {
+        let mut builder = CONTAINS_TWO_FALSE_DFA.clone().into_builder();
+        builder.transitions(changes.iter().copied()).unwrap();
+        builder.dead_state(None);
+
+        builder.build().unwrap()
+    }
+
+    fn detects(suite: &[Vec<bool>], implementation: &DFA<Boolean>) -> bool {
+        suite.iter().any(|test| {
+            CONTAINS_TWO_FALSE_DFA.accept(test.iter().copied())
+                != implementation.accept(test.iter().copied())
+        })
+    }
+
+    #[test]
+    fn covers_and_characterization() {
+        let dfa = &*CONTAINS_TWO_FALSE_DFA;
+
+        assert_eq!(
+            dfa.state_cover(),
+            vec![vec![], vec![false], vec![false, false]]
+        );
+        assert_eq!(dfa.transition_cover().len(), 7);
+
+        let characterization = dfa.characterization_set();
+        assert!(characterization.contains(&vec![]));
+        assert!(characterization.contains(&vec![false]));
+    }
+
+    #[test]
+    fn suites_detect_mutants() {
+        let mutants = vec![
+            mutant(&[(1, false, 1)]),
+            mutant(&[(0, true, 1)]),
+            mutant(&[(2, true, 0)]),
+        ];
+
+        for extra_states in 0..2 {
+            let w_suite = CONTAINS_TWO_FALSE_DFA.w_method(extra_states);
+            let wp_suite = CONTAINS_TWO_FALSE_DFA.wp_method(extra_states);
+
+            assert!(wp_suite.len() <= w_suite.len());
+            for implementation in &mutants {
+                assert!(detects(&w_suite, implementation));
+                assert!(detects(&wp_suite, implementation));
+            }
+        }
+    }
+
+    #[test]
+    fn suite_detects_extra_state() {
+        // Accepts after two `false`, except that a `true` immediately after the
+        // first pair resets, which needs one more state than the spec.
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        let q2 = builder.new_state();
+        let q3 = builder.new_state();
+        builder
+            .transitions(vec![
+                (q0, false, q1),
+                (q0, true, q0),
+                (q1, false, q2),
+                (q1, true, q0),
+                (q2, false, q3),
+                (q2, true, q0),
+                (q3, false, q3),
+                (q3, true, q3),
+            ])
+            .unwrap();
+        builder.start_state(q0).accept_states(vec![q2, q3]);
+        let implementation = builder.build().unwrap();
+
+        assert!(detects(
+            &CONTAINS_TWO_FALSE_DFA.wp_method(1),
+            &implementation
+        ));
+    }
+}
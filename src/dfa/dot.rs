@@ -0,0 +1,295 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::BTreeMap,
+    dfa::{DFABuilder, DFAStorage, DFA},
+    util::VecSet,
+};
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{Debug, Display, Write};
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord + Debug,
+    A: Alphabet,
+    A::Symbol: Clone,
+{
+    /// Render this DFA in the Graphviz DOT language.
+    ///
+    /// States are labelled with their label from
+    /// `DFABuilder::new_named_state`, or otherwise their `Debug`
+    /// representation. Accept states are drawn with a double circle and the
+    /// dead state with a dashed outline. All transitions between the same pair
+    /// of states are merged into a single edge, labelled with runs of
+    /// consecutive symbols (in the order of the alphabet) such as `a-c`.
+    pub fn to_dot(&self) -> String
+    where
+        A::Symbol: Display,
+    {
+        self.to_dot_with(|sym| format!("{}", sym))
+    }
+
+    /// Render this DFA in the Graphviz DOT language like `to_dot`, writing
+    /// each symbol with `symbol_label`. This also renders symbols that do not
+    /// implement `Display`, such as `()` or tuples.
+    pub fn to_dot_with<F>(&self, symbol_label: F) -> String
+    where
+        F: Fn(&A::Symbol) -> String,
+    {
+        render(
+            &self.storage,
+            Some(&self.start),
+            &self.accept,
+            self.dead.as_ref(),
+            &self.labels,
+            symbol_label,
+        )
+    }
+}
+
+impl<A, S> DFABuilder<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord + Debug,
+    A: Alphabet,
+    A::Symbol: Clone,
+{
+    /// Render the partially built DFA in the Graphviz DOT language.
+    ///
+    /// See `DFA::to_dot` for a description of the output. The start arrow is
+    /// omitted if no start state has been set, and start, accept and dead
+    /// states that have not been created are ignored.
+    pub fn to_dot(&self) -> String
+    where
+        A::Symbol: Display,
+    {
+        self.to_dot_with(|sym| format!("{}", sym))
+    }
+
+    /// Render the partially built DFA in the Graphviz DOT language, writing
+    /// each symbol with `symbol_label`. See `DFA::to_dot_with`.
+    pub fn to_dot_with<F>(&self, symbol_label: F) -> String
+    where
+        F: Fn(&A::Symbol) -> String,
+    {
+        render(
+            &self.storage,
+            self.start.as_ref(),
+            &self.accept,
+            self.dead.as_ref(),
+            &self.labels,
+            symbol_label,
+        )
+    }
+}
+
+fn render<A, S, F>(
+    storage: &S,
+    start: Option<&S::State>,
+    accept: &VecSet<S::State>,
+    dead: Option<&S::State>,
+    labels: &BTreeMap<S::State, String>,
+    symbol_label: F,
+) -> String
+where
+    S: DFAStorage<A>,
+    S::State: Ord + Debug,
+    A: Alphabet,
+    A::Symbol: Clone,
+    F: Fn(&A::Symbol) -> String,
+{
+    let states = storage.all_states();
+    let symbols: Vec<A::Symbol> = storage.alphabet().values().collect();
+    let nodes: BTreeMap<&S::State, usize> = states
+        .iter()
+        .enumerate()
+        .map(|(idx, state)| (state, idx))
+        .collect();
+
+    // Writing to a `String` cannot fail.
+    let mut dot = String::new();
+    writeln!(dot, "digraph dfa {{").unwrap();
+    writeln!(dot, "    rankdir=LR;").unwrap();
+    writeln!(dot, "    node [shape=circle];").unwrap();
+
+    if let Some(start) = start.and_then(|start| nodes.get(start)) {
+        writeln!(dot, "    start [shape=point];").unwrap();
+        writeln!(dot, "    start -> s{};", start).unwrap();
+    }
+
+    for (idx, state) in states.iter().enumerate() {
+        let label = match labels.get(state) {
+            Some(label) => label.clone(),
+            None => format!("{:?}", state),
+        };
+        write!(dot, "    s{} [label=\"{}\"", idx, escape(&label)).unwrap();
+        if accept.contains(state) {
+            write!(dot, ", shape=doublecircle").unwrap();
+        }
+        if dead == Some(state) {
+            write!(dot, ", style=dashed").unwrap();
+        }
+        writeln!(dot, "];").unwrap();
+    }
+
+    for (idx, state) in states.iter().enumerate() {
+        // Runs of consecutive symbols leading to the same state, grouped by
+        // that state in order of first appearance.
+        let mut edges: Vec<(usize, Vec<(usize, usize)>)> = Vec::new();
+        let mut previous: Option<usize> = None;
+
+        for (sym_idx, sym) in symbols.iter().enumerate() {
+            let target = storage
+                .transition(state.clone(), sym.clone())
+                .and_then(|target| nodes.get(&target).copied());
+
+            if let Some(target) = target {
+                let edge_idx = match edges.iter().position(|(other, _)| *other == target) {
+                    Some(edge_idx) => edge_idx,
+                    None => {
+                        edges.push((target, Vec::new()));
+                        edges.len() - 1
+                    }
+                };
+                let runs = &mut edges[edge_idx].1;
+
+                match runs.last_mut() {
+                    Some((_, end)) if previous == Some(target) => *end = sym_idx,
+                    _ => runs.push((sym_idx, sym_idx)),
+                }
+            }
+
+            previous = target;
+        }
+
+        for (target, runs) in edges {
+            let label = runs
+                .iter()
+                .map(|(first, last)| {
+                    if first == last {
+                        symbol_label(&symbols[*first])
+                    } else {
+                        format!(
+                            "{}-{}",
+                            symbol_label(&symbols[*first]),
+                            symbol_label(&symbols[*last])
+                        )
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(
+                dot,
+                "    s{} -> s{} [label=\"{}\"];",
+                idx,
+                target,
+                escape(&label)
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(dot, "}}").unwrap();
+
+    dot
+}
+
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        alphabet::{Alphabet, Boolean, Product, Unit},
+        dfa::DFABuilder,
+        test_helper::CONTAINS_TWO_FALSE_DFA,
+    };
+
+    #[test]
+    fn boolean_dfa() {
+        let dot = CONTAINS_TWO_FALSE_DFA.to_dot();
+
+        assert_eq!(
+            dot,
+            concat!(
+                "digraph dfa {\n",
+                "    rankdir=LR;\n",
+                "    node [shape=circle];\n",
+                "    start [shape=point];\n",
+                "    start -> s0;\n",
+                "    s0 [label=\"0\"];\n",
+                "    s1 [label=\"1\"];\n",
+                "    s2 [label=\"2\", shape=doublecircle, style=dashed];\n",
+                "    s0 -> s1 [label=\"false\"];\n",
+                "    s0 -> s0 [label=\"true\"];\n",
+                "    s1 -> s2 [label=\"false\"];\n",
+                "    s1 -> s0 [label=\"true\"];\n",
+                "    s2 -> s2 [label=\"false-true\"];\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn merges_symbol_ranges() {
+        let mut builder = DFABuilder::new('a'..='f');
+        let q0 = builder.new_state();
+        let q1 = builder.new_named_state("Done");
+        for sym in &['a', 'b', 'c', 'e'] {
+            builder.transition(q0, *sym, q1).unwrap();
+        }
+        builder.transition(q0, 'd', q0).unwrap();
+
+        let dot = builder.to_dot();
+        assert!(!dot.contains("start"));
+        assert!(dot.contains("s0 -> s1 [label=\"a-c, e\"];"));
+        assert!(dot.contains("s0 -> s0 [label=\"d\"];"));
+        assert!(!dot.contains("s1 ->"));
+
+        builder.start_state(q0).accept_states(vec![q1]);
+        let dot = builder.build().unwrap().to_dot();
+        assert!(dot.contains("start -> s0;"));
+        assert!(dot.contains("s1 [label=\"Done\", shape=doublecircle];"));
+    }
+
+    #[test]
+    fn ignores_unknown_states() {
+        let mut builder = DFABuilder::new('a'..='b');
+        let q0 = builder.new_state();
+        builder.transition(q0, 'a', q0).unwrap();
+        builder
+            .start_state(7)
+            .accept_states(vec![7])
+            .dead_state(Some(8));
+
+        let dot = builder.to_dot();
+        assert!(!dot.contains("start"));
+        assert!(dot.contains("s0 [label=\"0\"];"));
+        assert!(dot.contains("s0 -> s0 [label=\"a\"];"));
+    }
+
+    #[test]
+    fn symbols_without_display() {
+        let mut builder = DFABuilder::new(Unit);
+        let q0 = builder.new_named_state("line\nbreak");
+        builder.transition(q0, (), q0).unwrap();
+        builder.start_state(q0);
+
+        let dot = builder.build().unwrap().to_dot_with(|_| String::from("()"));
+        assert!(dot.contains("s0 [label=\"line\\nbreak\"];"));
+        assert!(dot.contains("s0 -> s0 [label=\"()\"];"));
+
+        let mut builder = DFABuilder::new(Product(Boolean, Boolean));
+        let q0 = builder.new_state();
+        for sym in builder.alphabet().values() {
+            builder.transition(q0, sym, q0).unwrap();
+        }
+        let dot = builder.to_dot_with(|(left, right)| format!("{}/{}", left, right));
+        assert!(dot.contains("s0 -> s0 [label=\"false/false-true/true\"];"));
+    }
+}
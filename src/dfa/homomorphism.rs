@@ -0,0 +1,369 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::{BTreeMap, VecDeque},
+    dfa::{DFABuilder, DFAStorage, DFA},
+    error::Error,
+    util::VecSet,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt::Debug;
+
+// A nondeterministic automaton over state indices and symbol indices, where a
+// `None` symbol is an empty transition.
+struct Nfa {
+    edges: Vec<Vec<(Option<usize>, usize)>>,
+}
+
+impl Nfa {
+    fn add_state(&mut self) -> usize {
+        self.edges.push(Vec::new());
+
+        self.edges.len() - 1
+    }
+
+    // Add every state reachable by empty transitions to the set.
+    fn closure(&self, states: impl IntoIterator<Item = usize>) -> VecSet<usize> {
+        let mut closure = VecSet::new();
+        let mut stack: Vec<usize> = states.into_iter().collect();
+
+        while let Some(state) = stack.pop() {
+            if closure.insert(state) {
+                stack.extend(
+                    self.edges[state]
+                        .iter()
+                        .filter(|(sym_idx, _)| sym_idx.is_none())
+                        .map(|(_, next)| *next),
+                );
+            }
+        }
+
+        closure
+    }
+
+    fn step(&self, states: &VecSet<usize>, sym_idx: usize) -> VecSet<usize> {
+        self.closure(states.iter().flat_map(|state| {
+            self.edges[*state]
+                .iter()
+                .filter(move |(edge_sym, _)| *edge_sym == Some(sym_idx))
+                .map(|(_, next)| *next)
+        }))
+    }
+}
+
+// Add a new state to the builder for every state of the DFA, along with the
+// start, dead and accept states and labels, returning the new state of each.
+fn copy_states<A, S, B, S2>(
+    dfa: &DFA<A, S>,
+    builder: &mut DFABuilder<B, S2>,
+) -> BTreeMap<S::State, S2::State>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    S2: DFAStorage<B>,
+    S2::State: Ord,
+    B: Alphabet,
+{
+    let state_mapping: BTreeMap<S::State, S2::State> = dfa
+        .storage
+        .all_states()
+        .into_iter()
+        .map(|state| (state, builder.new_state()))
+        .collect();
+
+    for (state, label) in &dfa.labels {
+        builder
+            .labels
+            .insert(state_mapping[state].clone(), label.clone());
+    }
+    builder
+        .start_state(state_mapping[&dfa.start].clone())
+        .dead_state(dfa.dead.as_ref().map(|dead| state_mapping[dead].clone()))
+        .accept_states(dfa.accept.iter().map(|state| state_mapping[state].clone()));
+
+    state_mapping
+}
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone,
+{
+    /// Construct a new DFA over another alphabet by renaming every symbol
+    /// with `f`, keeping the states, start, dead and accept states and labels.
+    ///
+    /// Symbols of the new alphabet that are not the image of any symbol have
+    /// no transitions, so strings containing them are rejected.
+    ///
+    /// # Error
+    ///
+    /// This function will error with `Error::SymbolNotInAlphabet` if `f`
+    /// returns a symbol outside of the new alphabet, and with
+    /// `Error::Nondeterministic` if two symbols are renamed to the same symbol
+    /// but lead to different states.
+    pub fn map_alphabet<B, S2, F>(&self, new_alphabet: B, f: F) -> Result<DFA<B, S2>, Error>
+    where
+        B: Alphabet,
+        B::Symbol: Clone + Debug,
+        S2: DFAStorage<B>,
+        S2::State: Ord + Debug,
+        F: Fn(&A::Symbol) -> B::Symbol,
+    {
+        let mut builder = DFABuilder::new_with_storage(S2::from_alphabet(new_alphabet));
+        let state_mapping = copy_states(self, &mut builder);
+
+        for (from, sym, to) in self.storage.all_transitions() {
+            let (from, to) = (&state_mapping[&from], state_mapping[&to].clone());
+            let new_sym = f(&sym);
+
+            match builder.storage.transition(from.clone(), new_sym.clone()) {
+                Some(previous) if previous != to => {
+                    return Err(Error::nondeterministic(from, &new_sym, None));
+                }
+                _ => builder.transition(from.clone(), new_sym, to)?,
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Construct a new DFA that accepts the image of the language of this DFA
+    /// under the string homomorphism `h`, which replaces every symbol with a
+    /// string over the new alphabet.
+    ///
+    /// The image is found with a nondeterministic automaton that reads `h(a)`
+    /// in place of each transition on `a`, and loops on `h(a)` for every `a`
+    /// in the dead state, which is then determinized with the subset
+    /// construction. The result only has the reachable subsets, and is
+    /// complete, with the empty subset as its dead state if it is reached.
+    ///
+    /// # Error
+    ///
+    /// This function will error with `Error::SymbolNotInAlphabet` if `h`
+    /// returns a symbol outside of the new alphabet.
+    pub fn homomorphism<B, S2, F>(&self, new_alphabet: B, h: F) -> Result<DFA<B, S2>, Error>
+    where
+        B: Alphabet,
+        B::Symbol: Clone + PartialEq + Debug,
+        S2: DFAStorage<B>,
+        S2::State: Ord,
+        F: Fn(&A::Symbol) -> Vec<B::Symbol>,
+    {
+        let symbols: Vec<B::Symbol> = new_alphabet.values().collect();
+        let states = self.storage.all_states();
+        let state_idx: BTreeMap<S::State, usize> = states
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(idx, state)| (state, idx))
+            .collect();
+
+        let mut nfa = Nfa {
+            edges: vec![Vec::new(); states.len()],
+        };
+        // The DFA stops reading in its dead state, so it loops there on every
+        // old symbol, whatever its transitions are.
+        let dead_loops = self.dead.iter().flat_map(|dead| {
+            self.storage
+                .alphabet()
+                .values()
+                .map(move |sym| (dead.clone(), sym, dead.clone()))
+        });
+        let transitions = self
+            .storage
+            .all_transitions()
+            .into_iter()
+            .filter(|(from, ..)| self.dead.as_ref() != Some(from))
+            .chain(dead_loops);
+        for (from, sym, to) in transitions {
+            let mut current = state_idx[&from];
+            let mut image = h(&sym)
+                .into_iter()
+                .map(|new_sym| {
+                    symbols
+                        .iter()
+                        .position(|candidate| *candidate == new_sym)
+                        .ok_or_else(|| Error::symbol_not_in_alphabet(&new_sym))
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            // Read all but the last symbol of the image through new states.
+            let last = image.pop();
+            for sym_idx in image {
+                let next = nfa.add_state();
+                nfa.edges[current].push((Some(sym_idx), next));
+                current = next;
+            }
+            nfa.edges[current].push((last, state_idx[&to]));
+        }
+
+        let accept: VecSet<usize> = self.accept.iter().map(|state| state_idx[state]).collect();
+
+        let mut builder = DFABuilder::new_with_storage(S2::from_alphabet(new_alphabet));
+        let mut subset_mapping: BTreeMap<VecSet<usize>, S2::State> = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        let mut new_accept = Vec::new();
+
+        let start = nfa.closure([state_idx[&self.start]]);
+        subset_mapping.insert(start.clone(), builder.new_state());
+        queue.push_back(start.clone());
+
+        while let Some(subset) = queue.pop_front() {
+            let new_state = subset_mapping[&subset].clone();
+            if subset.intersection(&accept).next().is_some() {
+                new_accept.push(new_state.clone());
+            }
+
+            for (sym_idx, sym) in symbols.iter().enumerate() {
+                let next = nfa.step(&subset, sym_idx);
+                let new_next = match subset_mapping.get(&next) {
+                    Some(new_next) => new_next.clone(),
+                    None => {
+                        let new_next = builder.new_state();
+                        subset_mapping.insert(next.clone(), new_next.clone());
+                        queue.push_back(next);
+                        new_next
+                    }
+                };
+
+                builder.add_transition(new_state.clone(), sym.clone(), new_next);
+            }
+        }
+
+        let dead = subset_mapping.get(&VecSet::new()).cloned();
+        builder
+            .start_state(subset_mapping[&start].clone())
+            .dead_state(dead)
+            .accept_states(new_accept);
+
+        Ok(builder.build_unchecked())
+    }
+
+    /// Construct a new DFA that accepts the strings over the new alphabet
+    /// whose image under the string homomorphism `h` is accepted by this DFA.
+    ///
+    /// The new DFA has the same states, and a transition on `b` leads to the
+    /// state reached by reading `h(b)` from the same state. There is no
+    /// transition on `b` if reading `h(b)` hits a missing transition.
+    pub fn inverse_homomorphism<B, S2, F>(&self, new_alphabet: B, h: F) -> Result<DFA<B, S2>, Error>
+    where
+        B: Alphabet,
+        B::Symbol: Clone,
+        S2: DFAStorage<B>,
+        S2::State: Ord,
+        F: Fn(&B::Symbol) -> Vec<A::Symbol>,
+    {
+        let symbols: Vec<B::Symbol> = new_alphabet.values().collect();
+        let images: Vec<Vec<A::Symbol>> = symbols.iter().map(h).collect();
+
+        let mut builder = DFABuilder::new_with_storage(S2::from_alphabet(new_alphabet));
+        let state_mapping = copy_states(self, &mut builder);
+
+        for (state, new_state) in &state_mapping {
+            for (sym, image) in symbols.iter().zip(&images) {
+                let mut current = Some(state.clone());
+                for old_sym in image {
+                    current = match current {
+                        Some(current) if self.dead.as_ref() == Some(&current) => Some(current),
+                        Some(current) => self.storage.transition(current, old_sym.clone()),
+                        None => break,
+                    };
+                }
+
+                if let Some(next) = current {
+                    builder.add_transition(
+                        new_state.clone(),
+                        sym.clone(),
+                        state_mapping[&next].clone(),
+                    );
+                }
+            }
+        }
+
+        Ok(builder.build_unchecked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        alphabet::{Boolean, IntoAlphabet},
+        dfa::DFA,
+        error::Error,
+        test_helper::{
+            binary_converter, convert_string, CONTAINS_EVEN_TRUES_DFA, CONTAINS_TWO_FALSE_DFA,
+        },
+        util::Range,
+    };
+    use proptest::{prop_assert_eq, proptest};
+
+    #[test]
+    fn map_alphabet_renames_symbols() {
+        let renamed: DFA<Boolean> = CONTAINS_TWO_FALSE_DFA
+            .map_alphabet(Boolean, |sym| !sym)
+            .unwrap();
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+            let negated: Vec<bool> = string.iter().map(|sym| !sym).collect();
+
+            prop_assert_eq!(renamed.accept(negated), CONTAINS_TWO_FALSE_DFA.accept(string));
+        });
+
+        let merged: Result<DFA<Boolean>, Error> =
+            CONTAINS_TWO_FALSE_DFA.map_alphabet(Boolean, |_| false);
+        assert!(matches!(merged, Err(Error::Nondeterministic { .. })));
+    }
+
+    #[test]
+    fn homomorphism_image() {
+        let code: DFA<Range<char>> = CONTAINS_TWO_FALSE_DFA
+            .homomorphism(('a'..='c').into_alphabet(), |sym| {
+                if *sym {
+                    vec!['c']
+                } else {
+                    vec!['a', 'b']
+                }
+            })
+            .unwrap();
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+            let image: Vec<char> = string
+                .iter()
+                .flat_map(|sym| if *sym { vec!['c'] } else { vec!['a', 'b'] })
+                .collect();
+
+            prop_assert_eq!(code.accept(image), CONTAINS_TWO_FALSE_DFA.accept(string));
+        });
+        assert!(!code.accept("abba".chars()));
+        assert!(!code.accept("ababa".chars()));
+        assert!(!code.accept("ababcb".chars()));
+        assert!(code.accept("ababcab".chars()));
+
+        // Erasing `false` leaves strings with an even number of `true`.
+        let erased: DFA<Boolean> = CONTAINS_EVEN_TRUES_DFA
+            .homomorphism(Boolean, |sym| if *sym { vec![true] } else { vec![] })
+            .unwrap();
+        assert!(erased.accept(vec![]));
+        assert!(erased.accept(vec![true, true]));
+        assert!(!erased.accept(vec![true]));
+        assert!(!erased.accept(vec![false]));
+    }
+
+    #[test]
+    fn inverse_homomorphism_preimage() {
+        let doubled: DFA<Boolean> = CONTAINS_TWO_FALSE_DFA
+            .inverse_homomorphism(Boolean, |sym| vec![*sym, *sym])
+            .unwrap();
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+            let contains_false = string.contains(&false);
+
+            prop_assert_eq!(doubled.accept(string), contains_false);
+        });
+    }
+}
@@ -1,103 +1,308 @@
 use crate::{
     alphabet::Alphabet,
+    collections::{BTreeMap, HashMap, VecDeque},
     dfa::{DFABuilder, DFAStorage, DFA},
     error::Error,
-    util::{CartesianProductIter, VecSet},
+    util::VecSet,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
 };
 use core::hash::Hash;
-use std::collections::HashMap;
 
-// Create a new DFA that is the cross product construction of the two given
-// DFAs, without initializing the accept states of the DFA.
-fn cross_product_construction_builder<SL, SR, SN, A>(
-    left: &DFA<A, SL>,
-    right: &DFA<A, SR>,
-) -> Result<
-    (
-        DFABuilder<A, SN>,
-        HashMap<(SL::State, SR::State), SN::State>,
-    ),
-    Error,
->
+// A pair of states from two DFAs, where `None` is the implicit rejecting sink
+// state reached by a missing transition.
+pub(super) type StatePair<L, R> = (Option<L>, Option<R>);
+
+// For each pair of states reached in a breadth first search, the pair and
+// symbol it was first reached from.
+type PairParents<L, R, Sym> = BTreeMap<StatePair<L, R>, Option<(StatePair<L, R>, Sym)>>;
+
+// The label of every state, or its position among the states of the DFA if it
+// has none.
+fn state_labels<A, S>(dfa: &DFA<A, S>) -> BTreeMap<S::State, String>
 where
-    SL: DFAStorage<A>,
-    SL::State: Ord + Hash,
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+{
+    dfa.storage
+        .all_states()
+        .into_iter()
+        .enumerate()
+        .map(|(idx, state)| {
+            let label = match dfa.labels.get(&state) {
+                Some(label) => label.clone(),
+                None => idx.to_string(),
+            };
+            (state, label)
+        })
+        .collect()
+}
 
-    SR: DFAStorage<A>,
-    SR::State: Ord + Hash,
+// The label of a state in a product, where the implicit sink state is `_`.
+fn sink_label<State: Ord>(labels: &BTreeMap<State, String>, state: &Option<State>) -> String {
+    match state {
+        Some(state) => labels[state].clone(),
+        None => String::from("_"),
+    }
+}
 
-    SN: DFAStorage<A>,
-    SN::State: Ord,
+// The states of a DFA, followed by `None` for the implicit rejecting sink state
+// if a state other than the dead state has a missing transition.
+fn states_with_sink<A, S>(dfa: &DFA<A, S>, symbols: &[A::Symbol]) -> Vec<Option<S::State>>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone,
+{
+    let states = dfa.storage.all_states();
+    let complete = states.iter().all(|state| {
+        dfa.dead.as_ref() == Some(state)
+            || symbols
+                .iter()
+                .all(|sym| dfa.storage.transition(state.clone(), sym.clone()).is_some())
+    });
 
-    A: Alphabet + PartialEq + Clone,
+    let mut states: Vec<Option<S::State>> = states.into_iter().map(Some).collect();
+    if !complete {
+        states.push(None);
+    }
+
+    states
+}
+
+// The state reached from one side of a pair via `sym`. Like `DFA::step`, the
+// dead state is absorbing, and the rejecting sink state stays where it is.
+fn side_step<A, S>(
+    storage: &S,
+    dead: Option<&S::State>,
+    state: &Option<S::State>,
+    sym: &A::Symbol,
+) -> Option<S::State>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
     A::Symbol: Clone,
 {
-    if left.storage.alphabet() != right.storage.alphabet() {
-        return Err(Error::OperationWithNonEqualAlphabets);
+    let state = state.as_ref()?;
+    if dead == Some(state) {
+        Some(state.clone())
+    } else {
+        storage.transition(state.clone(), sym.clone())
     }
+}
+
+// The states of the cross product construction of two DFAs, whose
+// transitions are found separately so that it can be done in parallel. A
+// missing transition is treated as a transition to a rejecting sink state,
+// and the dead state of either DFA is absorbing, as `DFA::accept` stops
+// reading there.
+pub(super) struct ProductPairs<'a, A, SL, SR, N>
+where
+    A: Alphabet,
+    SL: DFAStorage<A>,
+    SR: DFAStorage<A>,
+{
+    left: &'a SL,
+    right: &'a SR,
+    left_dead: Option<SL::State>,
+    right_dead: Option<SR::State>,
+    symbols: Vec<A::Symbol>,
+    pairs: Vec<StatePair<SL::State, SR::State>>,
+    mapping: HashMap<StatePair<SL::State, SR::State>, N>,
+}
+
+impl<'a, A, SL, SR, N> ProductPairs<'a, A, SL, SR, N>
+where
+    A: Alphabet + PartialEq + Clone,
+    A::Symbol: Clone,
+    SL: DFAStorage<A>,
+    SL::State: Ord + Hash,
+    SR: DFAStorage<A>,
+    SR::State: Ord + Hash,
+    N: Ord + Clone,
+{
+    // Create a builder with a state for every pair of states of the two
+    // DFAs, where a pair accepts if `accept` returns `true` given whether each
+    // state accepts. The name of the operation is used in errors.
+    pub(super) fn new<SN, F>(
+        left: &'a DFA<A, SL>,
+        right: &'a DFA<A, SR>,
+        operation: &'static str,
+        accept: F,
+    ) -> Result<(Self, DFABuilder<A, SN>), Error>
+    where
+        SN: DFAStorage<A, State = N>,
+        F: Fn(bool, bool) -> bool,
+    {
+        if left.storage.alphabet() != right.storage.alphabet() {
+            return Err(Error::OperationWithNonEqualAlphabets { operation });
+        }
 
-    let alphabet = left.storage.alphabet().clone();
-    let new_storage = SN::from_alphabet(alphabet);
-    let mut builder = DFABuilder::new_with_storage(new_storage);
+        let alphabet = left.storage.alphabet().clone();
+        let new_storage = SN::from_alphabet(alphabet);
+        let mut builder = DFABuilder::new_with_storage(new_storage);
+        let symbols: Vec<A::Symbol> = builder.alphabet().values().collect();
 
-    let mut state_mapping: HashMap<(SL::State, SR::State), SN::State> = HashMap::new();
+        let mut mapping: HashMap<StatePair<SL::State, SR::State>, N> = HashMap::new();
+        let mut pairs = Vec::new();
+        let right_states = states_with_sink(right, &symbols);
 
-    for left_state in left.storage.all_states() {
-        for right_state in right.storage.all_states() {
-            let new_state = builder.new_state();
+        for left_state in states_with_sink(left, &symbols) {
+            for right_state in &right_states {
+                let pair = (left_state.clone(), right_state.clone());
 
-            state_mapping.insert((left_state.clone(), right_state), new_state);
+                pairs.push(pair.clone());
+                mapping.insert(pair, builder.new_state());
+            }
+        }
+
+        // Label each pair when either side has labels.
+        if !left.labels.is_empty() || !right.labels.is_empty() {
+            let (left_labels, right_labels) = (state_labels(left), state_labels(right));
+            for ((left_state, right_state), new_state) in &mapping {
+                let label = format!(
+                    "({},{})",
+                    sink_label(&left_labels, left_state),
+                    sink_label(&right_labels, right_state)
+                );
+                builder.labels.insert(new_state.clone(), label);
+            }
+        }
+
+        let mut new_accept: VecSet<N> = VecSet::new();
+        for ((left_state, right_state), new_state) in &mapping {
+            let left_accepts = left_state.as_ref().is_some_and(|s| left.accept.contains(s));
+            let right_accepts = right_state
+                .as_ref()
+                .is_some_and(|s| right.accept.contains(s));
+
+            if accept(left_accepts, right_accepts) {
+                new_accept.insert(new_state.clone());
+            }
         }
-    }
 
-    builder.start_state(
-        state_mapping
-            .get(&(left.start.clone(), right.start.clone()))
-            .ok_or(Error::StateNotFound)?
-            .clone(),
-    );
-
-    builder.dead_state(
-        left.dead
-            .as_ref()
-            .cloned()
-            .and_then(|left_dead| {
-                right
-                    .dead
+        builder
+            .start_state(
+                mapping
+                    .get(&(Some(left.start.clone()), Some(right.start.clone())))
+                    .ok_or(Error::StateNotFound)?
+                    .clone(),
+            )
+            .dead_state(
+                left.dead
                     .as_ref()
                     .cloned()
-                    .map(|right_dead| (left_dead, right_dead))
-            })
-            .and_then(|dead_pair| state_mapping.get(&dead_pair).cloned()),
-    );
-
-    for ((self_state, other_state), new_state) in &state_mapping {
-        for sym in builder.alphabet().values() {
-            // This is safe bc self_state and sym both originate indirectly from
-            // left.storage
-            let self_next = unsafe {
-                left.storage
-                    .transition_unchecked(self_state.clone(), sym.clone())
-            };
+                    .and_then(|left_dead| {
+                        right
+                            .dead
+                            .as_ref()
+                            .cloned()
+                            .map(|right_dead| (Some(left_dead), Some(right_dead)))
+                    })
+                    .and_then(|dead_pair| mapping.get(&dead_pair).cloned()),
+            )
+            .accept_states(new_accept);
 
-            // This is safe bc other_state originates indirectly from other.storage. `sym`
-            // is safe to use here bc we asserted that `self`'s alphabet and `other`'s
-            // alphabet were the same at the top of this function.
-            let other_next = unsafe {
-                right
-                    .storage
-                    .transition_unchecked(other_state.clone(), sym.clone())
-            };
+        let product = ProductPairs {
+            left: &left.storage,
+            right: &right.storage,
+            left_dead: left.dead.clone(),
+            right_dead: right.dead.clone(),
+            symbols,
+            pairs,
+            mapping,
+        };
+
+        Ok((product, builder))
+    }
 
-            let new_next = state_mapping
-                .get(&(self_next, other_next))
-                .ok_or(Error::StateNotFound)?;
+    // The pairs of states, in the order that their successors are expected by
+    // `finish`.
+    pub(super) fn pairs(&self) -> &[StatePair<SL::State, SR::State>] {
+        &self.pairs
+    }
+
+    // The new state reached from a pair via each symbol.
+    pub(super) fn successors(
+        &self,
+        (left_state, right_state): &StatePair<SL::State, SR::State>,
+    ) -> Result<Vec<N>, Error> {
+        self.symbols
+            .iter()
+            .map(|sym| {
+                let left_next = side_step(self.left, self.left_dead.as_ref(), left_state, sym);
+                let right_next = side_step(self.right, self.right_dead.as_ref(), right_state, sym);
+
+                self.mapping
+                    .get(&(left_next, right_next))
+                    .cloned()
+                    .ok_or(Error::StateNotFound)
+            })
+            .collect()
+    }
 
-            builder.transition(new_state.clone(), sym, new_next.clone())?;
+    // Add the successors of every pair, in the order of `pairs`, and build the
+    // product DFA. States are numbered before the successors are found, so the
+    // result does not depend on how they were found.
+    pub(super) fn finish<SN>(
+        self,
+        mut builder: DFABuilder<A, SN>,
+        successors: Vec<Result<Vec<N>, Error>>,
+    ) -> Result<DFA<A, SN>, Error>
+    where
+        SN: DFAStorage<A, State = N>,
+    {
+        for (pair, nexts) in self.pairs.iter().zip(successors) {
+            let new_state = &self.mapping[pair];
+            for (sym, new_next) in self.symbols.iter().zip(nexts?) {
+                builder.add_transition(new_state.clone(), sym.clone(), new_next);
+            }
         }
+
+        Ok(builder.build_unchecked())
     }
+}
+
+// Create a new DFA that is the cross product construction of the two given
+// DFAs, where a pair of states accepts if `accept` returns `true` given whether
+// each state accepts. A missing transition is treated as a transition to a
+// rejecting sink state, and dead states are absorbing. The name of the
+// operation is used in errors.
+fn cross_product_construction<SL, SR, SN, A, F>(
+    left: &DFA<A, SL>,
+    right: &DFA<A, SR>,
+    operation: &'static str,
+    accept: F,
+) -> Result<DFA<A, SN>, Error>
+where
+    SL: DFAStorage<A>,
+    SL::State: Ord + Hash,
+
+    SR: DFAStorage<A>,
+    SR::State: Ord + Hash,
+
+    SN: DFAStorage<A>,
+    SN::State: Ord,
+
+    A: Alphabet + PartialEq + Clone,
+    A::Symbol: Clone,
+
+    F: Fn(bool, bool) -> bool,
+{
+    let (product, builder) = ProductPairs::new(left, right, operation, accept)?;
+    let successors = product
+        .pairs()
+        .iter()
+        .map(|pair| product.successors(pair))
+        .collect();
 
-    Ok((builder, state_mapping))
+    product.finish(builder, successors)
 }
 
 impl<A, S> DFA<A, S>
@@ -109,6 +314,9 @@ where
     /// Construct a new DFA that accepts the regular language that is the
     /// intersection of the regular languages represented by this DFA and
     /// another DFA.
+    ///
+    /// A missing transition is treated as a transition to a rejecting sink
+    /// state.
     pub fn intersection<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
     where
         S::State: Hash,
@@ -119,31 +327,15 @@ where
         A: PartialEq + Clone,
         A::Symbol: Clone,
     {
-        let (mut builder, state_mapping) =
-            cross_product_construction_builder::<S, S2, S3, A>(self, other)?;
-
-        // The intersection accept states is the cartesian product of the previous
-        // accept sets
-        let mut new_accept: VecSet<S3::State> = VecSet::new();
-        for self_accept_state in self.accept.iter().cloned() {
-            for other_accept_state in other.accept.iter().cloned() {
-                new_accept.insert(
-                    state_mapping
-                        .get(&(self_accept_state.clone(), other_accept_state))
-                        .ok_or(Error::StateNotFound)?
-                        .clone(),
-                );
-            }
-        }
-
-        builder.accept_states(new_accept);
-
-        builder.build()
+        cross_product_construction(self, other, "intersection", |left, right| left && right)
     }
 
     /// Construct a new DFA that accepts the regular language that is the
     /// union of the regular languages represented by this DFA and
     /// another DFA.
+    ///
+    /// A missing transition is treated as a transition to a rejecting sink
+    /// state.
     pub fn union<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
     where
         S::State: Hash,
@@ -154,37 +346,15 @@ where
         A: PartialEq + Clone,
         A::Symbol: Clone,
     {
-        let (mut builder, state_mapping) =
-            cross_product_construction_builder::<S, S2, S3, A>(self, other)?;
-
-        let other_all_states = other.storage.all_states();
-
-        // The union accept states are all states where either `left_state` or
-        // `right_state` was an accepting state.
-        let accept_pairs =
-            CartesianProductIter::new(self.storage.all_states().into_iter(), &other_all_states)
-                .filter(|(left_state, right_state)| {
-                    self.accept.contains(&left_state) || other.accept.contains(&right_state)
-                });
-
-        let mut new_accept: VecSet<S3::State> = VecSet::new();
-        for (left_state, right_state) in accept_pairs {
-            new_accept.insert(
-                state_mapping
-                    .get(&(left_state, right_state.clone()))
-                    .ok_or(Error::StateNotFound)?
-                    .clone(),
-            );
-        }
-
-        builder.accept_states(new_accept);
-
-        builder.build()
+        cross_product_construction(self, other, "union", |left, right| left || right)
     }
 
     /// Construct a new DFA that accepts the regular language that is the
     /// difference between regular languages represented by this DFA and
     /// another DFA.
+    ///
+    /// A missing transition is treated as a transition to a rejecting sink
+    /// state.
     pub fn difference<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
     where
         S::State: Hash,
@@ -195,32 +365,140 @@ where
         A: PartialEq + Clone,
         A::Symbol: Clone,
     {
-        let (mut builder, state_mapping) =
-            cross_product_construction_builder::<S, S2, S3, A>(self, other)?;
-
-        let other_all_states = other.storage.all_states();
-
-        // The difference accept states are all states where either `left_state` was an
-        // accepting state and `right_state` was not.
-        let accept_pairs =
-            CartesianProductIter::new(self.storage.all_states().into_iter(), &other_all_states)
-                .filter(|(left_state, right_state)| {
-                    self.accept.contains(&left_state) && !other.accept.contains(&right_state)
-                });
-
-        let mut new_accept: VecSet<S3::State> = VecSet::new();
-        for (left_state, right_state) in accept_pairs {
-            new_accept.insert(
-                state_mapping
-                    .get(&(left_state, right_state.clone()))
-                    .ok_or(Error::StateNotFound)?
-                    .clone(),
-            );
+        cross_product_construction(self, other, "difference", |left, right| left && !right)
+    }
+
+    /// Construct a new DFA that accepts the regular language that is the
+    /// symmetric difference between regular languages represented by this DFA
+    /// and another DFA.
+    ///
+    /// A missing transition is treated as a transition to a rejecting sink
+    /// state.
+    pub fn symmetric_difference<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
+    where
+        S::State: Hash,
+        S2: DFAStorage<A>,
+        S2::State: Ord + Hash,
+        S3: DFAStorage<A>,
+        S3::State: Ord,
+        A: PartialEq + Clone,
+        A::Symbol: Clone,
+    {
+        cross_product_construction(self, other, "symmetric_difference", |left, right| {
+            left != right
+        })
+    }
+
+    /// Construct a new DFA from the product of several DFAs, where a state
+    /// accepts if `accept` returns `true` for whether each DFA accepts.
+    ///
+    /// Only the product states reachable from the start are created, so no
+    /// intermediate products are built when combining many DFAs. For example,
+    /// `|accepts| accepts.iter().filter(|a| **a).count() == 2` accepts the
+    /// strings accepted by exactly two of the DFAs.
+    ///
+    /// A missing transition is treated as a transition to a rejecting sink
+    /// state, and each DFA stays at its dead state once it is reached.
+    ///
+    /// # Error
+    ///
+    /// This function will error if `dfas` is empty, or if the DFAs do not
+    /// all have the same alphabet.
+    pub fn product_all<S2, F>(dfas: &[&Self], accept: F) -> Result<DFA<A, S2>, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        A: PartialEq,
+        A::Symbol: Clone,
+        F: Fn(&[bool]) -> bool,
+    {
+        let first = dfas.first().ok_or(Error::NoOperands {
+            operation: "product_all",
+        })?;
+        if dfas
+            .iter()
+            .any(|dfa| dfa.storage.alphabet() != first.storage.alphabet())
+        {
+            return Err(Error::OperationWithNonEqualAlphabets {
+                operation: "product_all",
+            });
+        }
+
+        let new_storage = S2::from_alphabet(first.storage.alphabet().clone());
+        let mut builder = DFABuilder::new_with_storage(new_storage);
+        let symbols: Vec<A::Symbol> = builder.alphabet().values().collect();
+        let labels: Vec<BTreeMap<S::State, String>> =
+            if dfas.iter().any(|dfa| !dfa.labels.is_empty()) {
+                dfas.iter().map(|dfa| state_labels(dfa)).collect()
+            } else {
+                Vec::new()
+            };
+
+        // A tuple of states, where `None` is the implicit sink state.
+        let mut state_mapping: BTreeMap<Vec<Option<S::State>>, S2::State> = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        let mut new_accept = Vec::new();
+
+        let start: Vec<Option<S::State>> = dfas.iter().map(|dfa| Some(dfa.start.clone())).collect();
+        state_mapping.insert(start.clone(), builder.new_state());
+        queue.push_back(start.clone());
+
+        while let Some(states) = queue.pop_front() {
+            let new_state = state_mapping[&states].clone();
+
+            // Label each tuple of states when any DFA has labels.
+            if !labels.is_empty() {
+                let tuple: Vec<String> = labels
+                    .iter()
+                    .zip(&states)
+                    .map(|(labels, state)| sink_label(labels, state))
+                    .collect();
+                builder
+                    .labels
+                    .insert(new_state.clone(), format!("({})", tuple.join(",")));
+            }
+
+            let accepts: Vec<bool> = dfas
+                .iter()
+                .zip(&states)
+                .map(|(dfa, state)| state.as_ref().is_some_and(|s| dfa.accept.contains(s)))
+                .collect();
+            if accept(&accepts) {
+                new_accept.push(new_state.clone());
+            }
+
+            for sym in &symbols {
+                let next: Vec<Option<S::State>> = dfas
+                    .iter()
+                    .zip(&states)
+                    .map(|(dfa, state)| state.clone().and_then(|s| dfa.step(s, sym.clone())))
+                    .collect();
+
+                let new_next = match state_mapping.get(&next) {
+                    Some(new_next) => new_next.clone(),
+                    None => {
+                        let new_next = builder.new_state();
+                        state_mapping.insert(next.clone(), new_next.clone());
+                        queue.push_back(next);
+                        new_next
+                    }
+                };
+
+                builder.add_transition(new_state.clone(), sym.clone(), new_next);
+            }
         }
 
-        builder.accept_states(new_accept);
+        // The tuple of dead states only leads to itself, so it is dead.
+        let dead: Option<Vec<Option<S::State>>> =
+            dfas.iter().map(|dfa| dfa.dead.clone().map(Some)).collect();
+        let dead = dead.and_then(|dead| state_mapping.get(&dead).cloned());
+
+        builder
+            .start_state(state_mapping[&start].clone())
+            .dead_state(dead)
+            .accept_states(new_accept);
 
-        builder.build()
+        Ok(builder.build_unchecked())
     }
 
     /// Construct a new DFA that accepts the regular language that is the
@@ -239,16 +517,320 @@ where
 
         complement_dfa
     }
+
+    /// Find a shortest string that is accepted by exactly one of this DFA and
+    /// another DFA, or return `None` if the two DFAs accept the same language.
+    ///
+    /// A missing transition is treated as a transition to a rejecting sink
+    /// state.
+    pub fn distinguishing_string<S2>(
+        &self,
+        other: &DFA<A, S2>,
+    ) -> Result<Option<Vec<A::Symbol>>, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        A: PartialEq,
+        A::Symbol: Clone,
+    {
+        self.find_pair_string(
+            other,
+            "distinguishing_string",
+            |self_accepts, other_accepts| self_accepts != other_accepts,
+        )
+    }
+
+    /// Return `true` if this DFA and another DFA accept the same language.
+    pub fn is_equivalent<S2>(&self, other: &DFA<A, S2>) -> Result<bool, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        A: PartialEq,
+        A::Symbol: Clone,
+    {
+        Ok(self.distinguishing_string(other)?.is_none())
+    }
+
+    /// Find a shortest string that is accepted by both this DFA and another
+    /// DFA, or return `None` if their languages are disjoint.
+    ///
+    /// A missing transition is treated as a transition to a rejecting sink
+    /// state.
+    pub fn shared_string<S2>(&self, other: &DFA<A, S2>) -> Result<Option<Vec<A::Symbol>>, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        A: PartialEq,
+        A::Symbol: Clone,
+    {
+        self.find_pair_string(other, "shared_string", |self_accepts, other_accepts| {
+            self_accepts && other_accepts
+        })
+    }
+
+    /// Return `true` if no string is accepted by both this DFA and another
+    /// DFA. Use `shared_string` to find a witness when they are not disjoint.
+    pub fn is_disjoint<S2>(&self, other: &DFA<A, S2>) -> Result<bool, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        A: PartialEq,
+        A::Symbol: Clone,
+    {
+        Ok(self.shared_string(other)?.is_none())
+    }
+
+    // Find a shortest string that reaches a pair of states for which `found`
+    // returns `true` given whether each DFA accepts. A missing transition is
+    // treated as a transition to a rejecting sink state, and a dead state as
+    // absorbing, as in `accept`.
+    fn find_pair_string<S2, F>(
+        &self,
+        other: &DFA<A, S2>,
+        operation: &'static str,
+        found: F,
+    ) -> Result<Option<Vec<A::Symbol>>, Error>
+    where
+        S2: DFAStorage<A>,
+        S2::State: Ord,
+        A: PartialEq,
+        A::Symbol: Clone,
+        F: Fn(bool, bool) -> bool,
+    {
+        if self.storage.alphabet() != other.storage.alphabet() {
+            return Err(Error::OperationWithNonEqualAlphabets { operation });
+        }
+
+        let start = (Some(self.start.clone()), Some(other.start.clone()));
+        let mut parents: PairParents<S::State, S2::State, A::Symbol> = BTreeMap::new();
+        let mut queue = VecDeque::new();
+
+        parents.insert(start.clone(), None);
+        queue.push_back(start);
+
+        while let Some(pair) = queue.pop_front() {
+            let self_accepts = pair.0.as_ref().is_some_and(|s| self.accept.contains(s));
+            let other_accepts = pair.1.as_ref().is_some_and(|s| other.accept.contains(s));
+
+            if found(self_accepts, other_accepts) {
+                let mut string = Vec::new();
+                let mut current = &pair;
+                while let Some(Some((parent, sym))) = parents.get(current) {
+                    string.push(sym.clone());
+                    current = parent;
+                }
+                string.reverse();
+
+                return Ok(Some(string));
+            }
+
+            for sym in self.storage.alphabet().values() {
+                let next = (
+                    pair.0.clone().and_then(|s| self.step(s, sym.clone())),
+                    pair.1.clone().and_then(|s| other.step(s, sym.clone())),
+                );
+
+                if (next.0.is_some() || next.1.is_some()) && !parents.contains_key(&next) {
+                    parents.insert(next.clone(), Some((pair.clone(), sym)));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test_helper::{
-        binary_converter, convert_string, CONTAINS_EVEN_TRUES_DFA,
-        CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA, CONTAINS_TWO_FALSE_DFA,
+    use crate::{
+        alphabet::Boolean,
+        collections::BTreeSet,
+        dfa::{DFABuilder, DFAStorage, DFA},
+        error::Error,
+        test_helper::{
+            binary_converter, convert_string, CONTAINS_EVEN_TRUES_DFA,
+            CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA, CONTAINS_TWO_FALSE_DFA,
+        },
     };
     use proptest::{prop_assert, proptest};
 
+    // Contains a `false`, where the accepting dead state has no transitions.
+    fn contains_false() -> DFA<Boolean> {
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let dead = builder.new_state();
+        builder
+            .transitions(vec![(q0, false, dead), (q0, true, q0)])
+            .unwrap();
+        builder
+            .start_state(q0)
+            .dead_state(Some(dead))
+            .accept_states(vec![dead]);
+
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn products_keep_accepting_dead_states() {
+        let dfa = contains_false();
+
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        builder.start_state(q0);
+        let empty: DFA<Boolean> = builder.build().unwrap();
+
+        let union: DFA<Boolean> = dfa.union(&empty).unwrap();
+        let intersection: DFA<Boolean> = dfa.intersection(&dfa).unwrap();
+        let difference: DFA<Boolean> = dfa.difference(&empty).unwrap();
+        for product in [union, intersection, difference].iter() {
+            assert!(product.accept(vec![false]));
+            assert!(product.accept(vec![true, false, false, true]));
+            assert!(!product.accept(vec![true, true]));
+            assert!(product.is_equivalent(&dfa).unwrap());
+        }
+
+        let symmetric: DFA<Boolean> = dfa.symmetric_difference(&dfa.complement()).unwrap();
+        assert!(symmetric.accept(vec![]));
+        assert!(symmetric.accept(vec![true, false, true]));
+    }
+
+    #[test]
+    fn pair_search_stops_at_dead_states() {
+        let dfa = contains_false();
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        builder
+            .transitions(vec![
+                (q0, false, q1),
+                (q0, true, q0),
+                (q1, false, q1),
+                (q1, true, q1),
+            ])
+            .unwrap();
+        builder.start_state(q0).accept_states(vec![q1]);
+        let complete = builder.build().unwrap();
+
+        assert!(dfa.is_equivalent(&complete).unwrap());
+        assert!(complete.is_equivalent(&dfa).unwrap());
+        assert_eq!(
+            dfa.distinguishing_string(&*CONTAINS_TWO_FALSE_DFA).unwrap(),
+            Some(vec![false])
+        );
+        assert_eq!(
+            dfa.complement().distinguishing_string(&dfa).unwrap(),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn distinguishing_string_between_dfas() {
+        let witness = CONTAINS_TWO_FALSE_DFA
+            .distinguishing_string(&*CONTAINS_EVEN_TRUES_DFA)
+            .unwrap()
+            .unwrap();
+
+        assert_ne!(
+            CONTAINS_TWO_FALSE_DFA.accept(witness.iter().copied()),
+            CONTAINS_EVEN_TRUES_DFA.accept(witness.iter().copied())
+        );
+        // The empty string is accepted by only one of them
+        assert!(witness.is_empty());
+
+        assert!(CONTAINS_TWO_FALSE_DFA
+            .is_equivalent(&*CONTAINS_TWO_FALSE_DFA)
+            .unwrap());
+        let union: DFA<Boolean> = CONTAINS_TWO_FALSE_DFA
+            .union(&*CONTAINS_EVEN_TRUES_DFA)
+            .unwrap();
+        assert!(CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA
+            .is_equivalent(&union)
+            .unwrap());
+    }
+
+    #[test]
+    fn product_labels() {
+        let mut builder = DFABuilder::new(Boolean);
+        let idle = builder.new_named_state("Idle");
+        let busy = builder.new_named_state("Busy");
+        builder
+            .transitions(vec![
+                (idle, true, busy),
+                (idle, false, idle),
+                (busy, true, busy),
+                (busy, false, idle),
+            ])
+            .unwrap();
+        builder.start_state(idle).accept_states(vec![busy]);
+        let named = builder.build().unwrap();
+
+        let product: DFA<Boolean> = named.intersection(&*CONTAINS_TWO_FALSE_DFA).unwrap();
+        let labels: Vec<_> = product
+            .storage()
+            .all_states()
+            .iter()
+            .filter_map(|state| product.state_label(state))
+            .collect();
+        assert_eq!(labels.len(), 6);
+        assert!(labels.contains(&"(Idle,0)"));
+        assert!(labels.contains(&"(Busy,2)"));
+        assert_eq!(product.state_label(product.start_state()), Some("(Idle,0)"));
+
+        let unlabelled: DFA<Boolean> = CONTAINS_TWO_FALSE_DFA
+            .union(&*CONTAINS_EVEN_TRUES_DFA)
+            .unwrap();
+        assert_eq!(unlabelled.state_label(unlabelled.start_state()), None);
+    }
+
+    #[test]
+    fn symbols_without_debug() {
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Bit(bool);
+
+        let alphabet: BTreeSet<Bit> = [Bit(false), Bit(true)].iter().copied().collect();
+        let two_false: DFA<BTreeSet<Bit>> = CONTAINS_TWO_FALSE_DFA
+            .inverse_homomorphism(alphabet, |Bit(sym)| vec![*sym])
+            .unwrap();
+        let product: DFA<BTreeSet<Bit>> = two_false.intersection(&two_false).unwrap();
+
+        assert!(product.accept(vec![Bit(true), Bit(false), Bit(false)]));
+        assert!(!product.accept(vec![Bit(false), Bit(true)]));
+    }
+
+    #[test]
+    fn products_of_partial_dfas() {
+        // Accepts only the string `[true]`, with missing transitions.
+        let mut builder = DFABuilder::new(Boolean);
+        let start = builder.new_state();
+        let end = builder.new_state();
+        builder.transition(start, true, end).unwrap();
+        builder.start_state(start).accept_states(vec![end]);
+        let just_true = builder.build().unwrap();
+
+        let two_false = &*CONTAINS_TWO_FALSE_DFA;
+        let union: DFA<Boolean> = just_true.union(two_false).unwrap();
+        let intersection: DFA<Boolean> = just_true.intersection(two_false).unwrap();
+        let difference: DFA<Boolean> = two_false.difference(&just_true).unwrap();
+        let symmetric: DFA<Boolean> = just_true.symmetric_difference(two_false).unwrap();
+        let exactly_one: DFA<Boolean> = DFA::product_all(&[&just_true, two_false], |accepts| {
+            accepts.iter().filter(|accepts| **accepts).count() == 1
+        })
+        .unwrap();
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+            let left = string == [true];
+            let right = two_false.accept(string.iter().copied());
+
+            prop_assert!(union.accept(string.iter().copied()) == (left || right));
+            prop_assert!(intersection.accept(string.iter().copied()) == (left && right));
+            prop_assert!(difference.accept(string.iter().copied()) == (right && !left));
+            prop_assert!(symmetric.accept(string.iter().copied()) == (left != right));
+            prop_assert!(exactly_one.accept(string.iter().copied()) == (left != right));
+        });
+    }
+
     proptest! {
         #[test]
         fn accept_two_false_random(s in "(0|1)*00(0|1)*") {
@@ -271,4 +853,189 @@ mod tests {
             prop_assert!(CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA.accept(string));
         }
     }
+
+    #[test]
+    fn symmetric_difference_accepts_exactly_one() {
+        let symmetric_difference: DFA<Boolean> = CONTAINS_EVEN_TRUES_DFA
+            .symmetric_difference(&*CONTAINS_TWO_FALSE_DFA)
+            .unwrap();
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+
+            prop_assert!(
+                symmetric_difference.accept(string.clone())
+                    == (CONTAINS_EVEN_TRUES_DFA.accept(string.clone())
+                        != CONTAINS_TWO_FALSE_DFA.accept(string))
+            );
+        });
+
+        let empty: DFA<Boolean> = CONTAINS_TWO_FALSE_DFA
+            .symmetric_difference(&*CONTAINS_TWO_FALSE_DFA)
+            .unwrap();
+        assert!(empty.is_disjoint(&empty).unwrap());
+    }
+
+    #[test]
+    fn disjoint_dfas() {
+        let complement = CONTAINS_TWO_FALSE_DFA.complement();
+
+        assert!(CONTAINS_TWO_FALSE_DFA.is_disjoint(&complement).unwrap());
+        assert_eq!(
+            CONTAINS_TWO_FALSE_DFA
+                .shared_string(&*CONTAINS_EVEN_TRUES_DFA)
+                .unwrap(),
+            Some(vec![false, false])
+        );
+        assert!(!CONTAINS_TWO_FALSE_DFA
+            .is_disjoint(&*CONTAINS_EVEN_TRUES_DFA)
+            .unwrap());
+    }
+
+    #[test]
+    fn disjoint_with_complemented_dead_states() {
+        let contains_false = contains_false();
+        let all_true = contains_false.complement();
+
+        assert!(contains_false.is_disjoint(&all_true).unwrap());
+        assert!(all_true.is_disjoint(&contains_false).unwrap());
+        assert_eq!(
+            contains_false
+                .shared_string(&*CONTAINS_TWO_FALSE_DFA)
+                .unwrap(),
+            Some(vec![false, false])
+        );
+        assert!(!all_true
+            .is_disjoint(&CONTAINS_TWO_FALSE_DFA.complement())
+            .unwrap());
+
+        let everything: DFA<Boolean> = contains_false.symmetric_difference(&all_true).unwrap();
+        assert!(everything.accept(vec![false, true]));
+        assert!(everything.accept(vec![true, true, false, false]));
+        assert!(everything.is_disjoint(&everything.complement()).unwrap());
+    }
+
+    #[test]
+    fn product_all_matches_binary_operations() {
+        let dfas = [&*CONTAINS_EVEN_TRUES_DFA, &*CONTAINS_TWO_FALSE_DFA];
+
+        let all: DFA<Boolean> =
+            DFA::product_all(&dfas, |accepts| accepts.iter().all(|a| *a)).unwrap();
+        let intersection: DFA<Boolean> = CONTAINS_EVEN_TRUES_DFA
+            .intersection(&*CONTAINS_TWO_FALSE_DFA)
+            .unwrap();
+        assert!(all.is_equivalent(&intersection).unwrap());
+
+        let any: DFA<Boolean> =
+            DFA::product_all(&dfas, |accepts| accepts.iter().any(|a| *a)).unwrap();
+        assert!(any
+            .is_equivalent(&*CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA)
+            .unwrap());
+    }
+
+    #[test]
+    fn product_all_only_creates_reachable_states() {
+        let dfa = &*CONTAINS_TWO_FALSE_DFA;
+        let product: DFA<Boolean> =
+            DFA::product_all(&[dfa, dfa, dfa], |accepts| accepts[0]).unwrap();
+
+        assert_eq!(
+            product.storage().all_states().len(),
+            dfa.storage().all_states().len()
+        );
+        assert!(product.is_equivalent(dfa).unwrap());
+    }
+
+    #[test]
+    fn product_all_of_nothing() {
+        let product: Result<DFA<Boolean>, Error> = DFA::<Boolean>::product_all(&[], |_| true);
+
+        assert!(matches!(
+            product,
+            Err(Error::NoOperands {
+                operation: "product_all"
+            })
+        ));
+    }
+
+    #[test]
+    fn product_all_exactly_two() {
+        let dfas = [
+            &*CONTAINS_EVEN_TRUES_DFA,
+            &*CONTAINS_TWO_FALSE_DFA,
+            &*CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA,
+        ];
+        let product: DFA<Boolean> =
+            DFA::product_all(&dfas, |accepts| accepts.iter().filter(|a| **a).count() == 2).unwrap();
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+            let count = dfas.iter().filter(|dfa| dfa.accept(string.clone())).count();
+
+            prop_assert!(product.accept(string) == (count == 2));
+        });
+    }
+
+    #[test]
+    fn product_all_of_complements() {
+        let contains_false = contains_false();
+        let all_true = contains_false.complement();
+        let two_false = CONTAINS_TWO_FALSE_DFA.complement();
+        let dfas = [&contains_false, &all_true, &two_false];
+
+        let one: DFA<Boolean> =
+            DFA::product_all(&dfas[..2], |accepts| accepts[0] != accepts[1]).unwrap();
+        assert!(one.dead_state().is_some());
+        assert!(one.accept(vec![]));
+        assert!(one.accept(vec![true, false, true, false]));
+
+        let product: DFA<Boolean> =
+            DFA::product_all(&dfas, |accepts| accepts.iter().filter(|a| **a).count() == 2).unwrap();
+        assert!(product.accept(vec![false, true]));
+        assert!(!product.accept(vec![true, false, false]));
+        assert!(product.accept(vec![true]));
+
+        proptest!(|(string in "[01]*")| {
+            let string = convert_string(string, binary_converter());
+            let count = dfas.iter().filter(|dfa| dfa.accept(string.clone())).count();
+
+            prop_assert!(product.accept(string) == (count == 2));
+        });
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn product_does_not_depend_on_thread_count() {
+        let product = || -> DFA<Boolean> {
+            CONTAINS_EVEN_TRUES_DFA
+                .par_union(&*CONTAINS_TWO_FALSE_DFA)
+                .unwrap()
+        };
+        let sequential: DFA<Boolean> = CONTAINS_EVEN_TRUES_DFA
+            .union(&*CONTAINS_TWO_FALSE_DFA)
+            .unwrap();
+        let single = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap()
+            .install(product);
+        let many = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap()
+            .install(product);
+
+        for parallel in [single, many].iter() {
+            assert_eq!(parallel.start_state(), sequential.start_state());
+            assert_eq!(parallel.accept_states(), sequential.accept_states());
+            for state in sequential.storage().all_states() {
+                for sym in [false, true].iter().copied() {
+                    assert_eq!(
+                        parallel.storage().transition(state, sym),
+                        sequential.storage().transition(state, sym)
+                    );
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,102 @@
+use crate::{
+    alphabet::Alphabet,
+    dfa::{operations::ProductPairs, DFAStorage, DFA},
+    error::Error,
+};
+use core::hash::Hash;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+// Like `cross_product_construction`, but the transitions of each pair of
+// states are found in parallel.
+fn par_cross_product_construction<SL, SR, SN, A, F>(
+    left: &DFA<A, SL>,
+    right: &DFA<A, SR>,
+    operation: &'static str,
+    accept: F,
+) -> Result<DFA<A, SN>, Error>
+where
+    SL: DFAStorage<A> + Sync,
+    SL::State: Ord + Hash + Send + Sync,
+
+    SR: DFAStorage<A> + Sync,
+    SR::State: Ord + Hash + Send + Sync,
+
+    SN: DFAStorage<A>,
+    SN::State: Ord + Send + Sync,
+
+    A: Alphabet + PartialEq + Clone,
+    A::Symbol: Clone + Sync,
+
+    F: Fn(bool, bool) -> bool,
+{
+    let (product, builder) = ProductPairs::new(left, right, operation, accept)?;
+    let successors = product
+        .pairs()
+        .par_iter()
+        .map(|pair| product.successors(pair))
+        .collect();
+
+    product.finish(builder, successors)
+}
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A> + Sync,
+    S::State: Ord + Hash + Send + Sync,
+    A: Alphabet + PartialEq + Clone,
+    A::Symbol: Clone + Sync,
+{
+    /// Construct the intersection of this DFA and another DFA like
+    /// `intersection`, finding the transitions of the product in parallel.
+    ///
+    /// States are numbered before the transitions are found, so the result is
+    /// the same as that of `intersection` for any number of threads.
+    pub fn par_intersection<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
+    where
+        S2: DFAStorage<A> + Sync,
+        S2::State: Ord + Hash + Send + Sync,
+        S3: DFAStorage<A>,
+        S3::State: Ord + Send + Sync,
+    {
+        par_cross_product_construction(self, other, "intersection", |left, right| left && right)
+    }
+
+    /// Construct the union of this DFA and another DFA like `union`, finding
+    /// the transitions of the product in parallel.
+    pub fn par_union<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
+    where
+        S2: DFAStorage<A> + Sync,
+        S2::State: Ord + Hash + Send + Sync,
+        S3: DFAStorage<A>,
+        S3::State: Ord + Send + Sync,
+    {
+        par_cross_product_construction(self, other, "union", |left, right| left || right)
+    }
+
+    /// Construct the difference of this DFA and another DFA like
+    /// `difference`, finding the transitions of the product in parallel.
+    pub fn par_difference<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
+    where
+        S2: DFAStorage<A> + Sync,
+        S2::State: Ord + Hash + Send + Sync,
+        S3: DFAStorage<A>,
+        S3::State: Ord + Send + Sync,
+    {
+        par_cross_product_construction(self, other, "difference", |left, right| left && !right)
+    }
+
+    /// Construct the symmetric difference of this DFA and another DFA like
+    /// `symmetric_difference`, finding the transitions of the product in
+    /// parallel.
+    pub fn par_symmetric_difference<S2, S3>(&self, other: &DFA<A, S2>) -> Result<DFA<A, S3>, Error>
+    where
+        S2: DFAStorage<A> + Sync,
+        S2::State: Ord + Hash + Send + Sync,
+        S3: DFAStorage<A>,
+        S3::State: Ord + Send + Sync,
+    {
+        par_cross_product_construction(self, other, "symmetric_difference", |left, right| {
+            left != right
+        })
+    }
+}
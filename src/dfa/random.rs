@@ -0,0 +1,165 @@
+use crate::{
+    alphabet::{Alphabet, IntoAlphabet},
+    dfa::{DFABuilder, DFA},
+    util::uniform_below,
+};
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, hash::Hash};
+use rand_core::Rng;
+
+impl<A> DFA<A>
+where
+    A: Alphabet,
+    A::Symbol: Clone + Eq + Hash,
+{
+    /// Generate a random accessible complete DFA with exactly `num_states`
+    /// states, chosen uniformly among all such DFAs up to isomorphism. Each
+    /// state is independently an accept state with probability 1/2, and no
+    /// dead state is set.
+    ///
+    /// The generator draws random complete transition functions over a
+    /// slightly larger number of states and keeps the accessible part when it
+    /// has exactly `num_states` states, following Carayol and Nicaud. Every
+    /// accessible DFA is the accessible part of equally many transition
+    /// functions, so the result is uniform. The expected number of attempts
+    /// grows with the square root of `num_states`.
+    ///
+    /// States are numbered in breadth first order from the start state, taking
+    /// symbols in the order of the alphabet, so equal random streams give
+    /// equal DFAs.
+    ///
+    /// This function requires the `rand` feature.
+    ///
+    /// # Panics
+    /// This function will panic if `num_states` is zero, or if it is greater
+    /// than one and the alphabet is empty.
+    pub fn random<I, R>(alphabet: I, num_states: usize, rng: &mut R) -> Self
+    where
+        I: IntoAlphabet<IntoAlpha = A, Symbol = A::Symbol>,
+        R: Rng,
+    {
+        let mut builder = DFABuilder::new(alphabet);
+        let symbols: Vec<A::Symbol> = builder.alphabet().values().collect();
+        let num_symbols = symbols.len();
+        assert!(num_states > 0, "a DFA needs at least one state");
+        assert!(
+            num_symbols > 0 || num_states == 1,
+            "only one state is accessible without symbols"
+        );
+
+        // The number of states to draw transitions over. Since the accepted
+        // results are uniform for any choice, it is adjusted towards the size
+        // whose accessible part most often has `num_states` states.
+        let mut num_drawn = num_states;
+        let (table, order) = loop {
+            let table: Vec<usize> = (0..num_drawn * num_symbols)
+                .map(|_| uniform_below(rng, num_drawn))
+                .collect();
+            let order = accessible_order(&table, num_drawn, num_symbols, num_states);
+
+            match order.len().cmp(&num_states) {
+                Ordering::Equal => break (table, order),
+                Ordering::Less => num_drawn += 1,
+                Ordering::Greater if num_drawn > num_states => num_drawn -= 1,
+                Ordering::Greater => {}
+            }
+        };
+
+        let mut renumber = vec![0; num_drawn];
+        let states: Vec<usize> = order
+            .iter()
+            .enumerate()
+            .map(|(idx, old)| {
+                renumber[*old] = idx;
+                builder.new_state()
+            })
+            .collect();
+
+        for (idx, old) in order.iter().enumerate() {
+            for (sym_idx, sym) in symbols.iter().enumerate() {
+                let target = states[renumber[table[old * num_symbols + sym_idx]]];
+                builder.add_transition(states[idx], sym.clone(), target);
+            }
+        }
+
+        let accept: Vec<usize> = states
+            .iter()
+            .copied()
+            .filter(|_| uniform_below(rng, 2) == 1)
+            .collect();
+        builder.start_state(states[0]).accept_states(accept);
+
+        builder.build().expect("generated DFA is valid")
+    }
+}
+
+// Return the states reachable from state 0 in breadth first order, stopping
+// once more than `limit` states are found.
+fn accessible_order(
+    table: &[usize],
+    num_states: usize,
+    num_symbols: usize,
+    limit: usize,
+) -> Vec<usize> {
+    let mut seen = vec![false; num_states];
+    let mut order = vec![0];
+    seen[0] = true;
+
+    let mut idx = 0;
+    while idx < order.len() && order.len() <= limit {
+        let state = order[idx];
+        for target in &table[state * num_symbols..(state + 1) * num_symbols] {
+            if !seen[*target] {
+                seen[*target] = true;
+                order.push(*target);
+            }
+        }
+        idx += 1;
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alphabet::Boolean, collections::HashMap, dfa::DFAStorage};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn accessible_and_complete() {
+        let mut rng = XorShiftRng::seed_from_u64(7);
+
+        for num_states in 1..20 {
+            let dfa = DFA::random('a'..='c', num_states, &mut rng);
+            let report = dfa.clone().into_builder().validate();
+
+            assert_eq!(dfa.storage().all_states().len(), num_states);
+            assert!(report.is_valid());
+        }
+
+        let first = DFA::random(Boolean, 30, &mut XorShiftRng::seed_from_u64(1));
+        let second = DFA::random(Boolean, 30, &mut XorShiftRng::seed_from_u64(1));
+        assert_eq!(first.to_dot(), second.to_dot());
+    }
+
+    #[test]
+    fn uniform_over_small_dfas() {
+        // There are 12 accessible complete DFAs with 2 states over 2 symbols,
+        // and 4 choices of accept states for each.
+        let mut rng = XorShiftRng::seed_from_u64(3);
+        let mut counts: HashMap<_, usize> = HashMap::new();
+        for _ in 0..48_000 {
+            let dfa = DFA::random(Boolean, 2, &mut rng);
+            let mut transitions = dfa.storage().all_transitions();
+            transitions.sort_unstable();
+            *counts
+                .entry((transitions, dfa.accept_states().to_vec()))
+                .or_insert(0) += 1;
+        }
+
+        assert_eq!(counts.len(), 48);
+        assert!(counts.values().all(|count| (850..1150).contains(count)));
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::{hash_map::Entry, HashMap},
+    dfa::{DFABuilder, DFAStorage, DefaultDFAStorage, DFA},
+    error::Error,
+    util::VecSet,
+};
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Debug, hash::Hash};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+// The transitions are stored as a list, since most formats cannot use a pair
+// as the key of a map.
+#[derive(Serialize)]
+#[serde(rename = "DefaultDFAStorage")]
+struct StorageRef<'a, A, Sym> {
+    alphabet: &'a A,
+    num_states: usize,
+    removed: &'a VecSet<usize>,
+    transitions: Vec<(usize, &'a Sym, usize)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "DefaultDFAStorage")]
+struct StorageRepr<A, Sym> {
+    alphabet: A,
+    num_states: usize,
+    #[serde(default)]
+    removed: VecSet<usize>,
+    transitions: Vec<(usize, Sym, usize)>,
+}
+
+impl<A> Serialize for DefaultDFAStorage<A, A::Symbol>
+where
+    A: Alphabet + Serialize,
+    A::Symbol: Eq + Hash + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StorageRef {
+            alphabet: &self.alphabet,
+            num_states: self.next_state,
+            removed: &self.removed,
+            transitions: self
+                .transition
+                .iter()
+                .map(|((from, sym), to)| (*from, sym, *to))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, A> Deserialize<'de> for DefaultDFAStorage<A, A::Symbol>
+where
+    A: Alphabet + Deserialize<'de>,
+    A::Symbol: Eq + Hash + Debug + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let StorageRepr {
+            alphabet,
+            num_states,
+            removed,
+            transitions,
+        } = StorageRepr::<A, A::Symbol>::deserialize(deserializer)?;
+
+        let valid = |state: usize| state < num_states && !removed.contains(&state);
+        if let Some(state) = removed.iter().find(|state| **state >= num_states) {
+            return Err(de::Error::custom(Error::invalid_state(state)));
+        }
+
+        let mut transition = HashMap::with_capacity(transitions.len());
+        for (from, sym, to) in transitions {
+            if !valid(from) {
+                return Err(de::Error::custom(Error::invalid_state(&from)));
+            }
+            if !valid(to) {
+                return Err(de::Error::custom(Error::invalid_state(&to)));
+            }
+            if !alphabet.contains(&sym) {
+                return Err(de::Error::custom(Error::symbol_not_in_alphabet(&sym)));
+            }
+            match transition.entry((from, sym)) {
+                Entry::Occupied(entry) => {
+                    let (from, sym) = entry.key();
+                    return Err(de::Error::custom(Error::nondeterministic(from, sym, None)));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(to);
+                }
+            }
+        }
+
+        Ok(DefaultDFAStorage {
+            alphabet,
+            next_state: num_states,
+            removed,
+            transition,
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "DFA")]
+struct DFARef<'a, State, S> {
+    start: &'a State,
+    accept: &'a VecSet<State>,
+    dead: Option<&'a State>,
+    storage: &'a S,
+    labels: Vec<(&'a State, &'a String)>,
+}
+
+#[derive(Deserialize)]
+#[serde(
+    rename = "DFA",
+    bound(deserialize = "State: Ord + Deserialize<'de>, S: Deserialize<'de>")
+)]
+struct DFARepr<State, S> {
+    start: State,
+    accept: VecSet<State>,
+    dead: Option<State>,
+    storage: S,
+    #[serde(default)]
+    labels: Vec<(State, String)>,
+}
+
+impl<A, S> Serialize for DFA<A, S>
+where
+    S: DFAStorage<A> + Serialize,
+    S::State: Serialize,
+    A: Alphabet,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        DFARef {
+            start: &self.start,
+            accept: &self.accept,
+            dead: self.dead.as_ref(),
+            storage: &self.storage,
+            labels: self.labels.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, A, S> Deserialize<'de> for DFA<A, S>
+where
+    S: DFAStorage<A> + Deserialize<'de>,
+    S::State: Ord + Debug + Deserialize<'de>,
+    A: Alphabet,
+{
+    /// Deserialize a DFA, performing the same checks as `DFABuilder::build`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let DFARepr {
+            start,
+            accept,
+            dead,
+            storage,
+            labels,
+        } = DFARepr::<S::State, S>::deserialize(deserializer)?;
+
+        if let Some((state, _)) = labels.iter().find(|(s, _)| !storage.contains_state(s)) {
+            return Err(de::Error::custom(Error::invalid_state(state)));
+        }
+
+        DFABuilder {
+            start: Some(start),
+            accept,
+            dead,
+            storage,
+            labels: labels.into_iter().collect(),
+            overwritten: Vec::new(),
+        }
+        .build()
+        .map_err(de::Error::custom)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "DFABuilder")]
+struct BuilderRef<'a, State, S> {
+    start: Option<&'a State>,
+    accept: &'a VecSet<State>,
+    dead: Option<&'a State>,
+    storage: &'a S,
+    labels: Vec<(&'a State, &'a String)>,
+}
+
+#[derive(Deserialize)]
+#[serde(
+    rename = "DFABuilder",
+    bound(deserialize = "State: Ord + Deserialize<'de>, S: Deserialize<'de>")
+)]
+struct BuilderRepr<State, S> {
+    start: Option<State>,
+    accept: VecSet<State>,
+    dead: Option<State>,
+    storage: S,
+    #[serde(default)]
+    labels: Vec<(State, String)>,
+}
+
+impl<A, S> Serialize for DFABuilder<A, S>
+where
+    S: DFAStorage<A> + Serialize,
+    S::State: Serialize,
+    A: Alphabet,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        BuilderRef {
+            start: self.start.as_ref(),
+            accept: &self.accept,
+            dead: self.dead.as_ref(),
+            storage: &self.storage,
+            labels: self.labels.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, A, S> Deserialize<'de> for DFABuilder<A, S>
+where
+    S: DFAStorage<A> + Deserialize<'de>,
+    S::State: Ord + Deserialize<'de>,
+    A: Alphabet,
+{
+    /// Deserialize a builder. Only the storage is validated, the remaining
+    /// checks are left to `DFABuilder::build`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let BuilderRepr {
+            start,
+            accept,
+            dead,
+            storage,
+            labels,
+        } = BuilderRepr::<S::State, S>::deserialize(deserializer)?;
+
+        Ok(DFABuilder {
+            start,
+            accept,
+            dead,
+            storage,
+            labels: labels.into_iter().collect(),
+            overwritten: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        alphabet::Boolean,
+        dfa::{DFABuilder, DFA},
+        test_helper::CONTAINS_TWO_FALSE_DFA,
+        util::Range,
+    };
+
+    #[test]
+    fn round_trip() {
+        let json = serde_json::to_string(&*CONTAINS_TWO_FALSE_DFA).unwrap();
+        let dfa: DFA<Boolean> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(dfa.start_state(), CONTAINS_TWO_FALSE_DFA.start_state());
+        assert_eq!(dfa.accept_states(), CONTAINS_TWO_FALSE_DFA.accept_states());
+        assert_eq!(dfa.dead_state(), CONTAINS_TWO_FALSE_DFA.dead_state());
+        assert!(dfa.is_equivalent(&*CONTAINS_TWO_FALSE_DFA).unwrap());
+
+        let mut builder = DFABuilder::new('a'..='c');
+        let q0 = builder.new_named_state("Start");
+        builder.transition(q0, 'b', q0).unwrap();
+        let json = serde_json::to_string(&builder).unwrap();
+        let mut builder: DFABuilder<Range<char>> = serde_json::from_str(&json).unwrap();
+        builder.start_state(q0).accept_states(vec![q0]);
+        let dfa = builder.build().unwrap();
+
+        assert_eq!(dfa.state_label(&q0), Some("Start"));
+        assert!(dfa.accept("bb".chars()));
+        assert!(!dfa.accept("ab".chars()));
+    }
+
+    #[test]
+    fn rejects_invalid_data() {
+        let storage = r#"{"alphabet":null,"num_states":2,"transitions":[[0,true,1],[1,false,0]]}"#;
+        let parse = |start: &str, accept: &str, storage: &str| {
+            let json = format!(
+                r#"{{"start":{},"accept":{},"dead":null,"storage":{}}}"#,
+                start, accept, storage
+            );
+            serde_json::from_str::<DFA<Boolean>>(&json)
+        };
+
+        assert!(parse("0", "[1]", storage).is_ok());
+        assert!(parse("2", "[1]", storage).is_err());
+        assert!(parse("0", "[2]", storage).is_err());
+        assert!(parse(
+            "0",
+            "[]",
+            r#"{"alphabet":null,"num_states":1,"transitions":[[0,true,1]]}"#
+        )
+        .is_err());
+        assert!(parse(
+            "0",
+            "[]",
+            r#"{"alphabet":null,"num_states":1,"transitions":[[0,true,0],[0,true,0]]}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<DFA<Range<char>>>(
+            r#"{"start":0,"accept":[],"dead":null,"storage":{"alphabet":{"NonEmpty":{"start":"a","end":"b"}},"num_states":1,"transitions":[[0,"z",0]]}}"#
+        )
+        .is_err());
+
+        assert!(
+            serde_json::from_str::<Range<char>>(r#"{"NonEmpty":{"start":"a","end":"b"}}"#).is_ok()
+        );
+        assert!(
+            serde_json::from_str::<Range<char>>(r#"{"NonEmpty":{"start":"b","end":"a"}}"#).is_err()
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::{hash_map::Entry, BTreeMap, HashMap, VecDeque},
+    dfa::{DFAStorage, DFA},
+    util::VecSet,
+};
+use alloc::{vec, vec::Vec};
+
+// Automata with at most this many states are synchronized with an exhaustive
+// search, which finds a shortest word.
+const EXACT_SEARCH_LIMIT: usize = 16;
+
+// For each node reached in a breadth first search, the node and symbol index it
+// was first reached from.
+type Parents<Node> = HashMap<Node, Option<(Node, usize)>>;
+
+// The transition table of a DFA over state indices. A missing transition leads
+// to an extra sink state, which loops to itself on every symbol.
+struct Table<Sym> {
+    symbols: Vec<Sym>,
+    next: Vec<Vec<usize>>,
+}
+
+impl<Sym> Table<Sym>
+where
+    Sym: Clone,
+{
+    fn num_states(&self) -> usize {
+        self.next.len()
+    }
+
+    fn image(&self, states: &VecSet<usize>, sym_idx: usize) -> VecSet<usize> {
+        states
+            .iter()
+            .map(|state| self.next[*state][sym_idx])
+            .collect()
+    }
+
+    // Breadth first search over pairs of states for a shortest word that takes
+    // both states to the same state.
+    fn merging_word(&self, p: usize, q: usize) -> Option<Vec<usize>> {
+        let mut parents: Parents<(usize, usize)> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        parents.insert((p, q), None);
+        queue.push_back((p, q));
+
+        while let Some(pair) = queue.pop_front() {
+            if pair.0 == pair.1 {
+                let mut word = Vec::new();
+                let mut current = pair;
+                while let Some(Some((parent, sym_idx))) = parents.get(&current) {
+                    word.push(*sym_idx);
+                    current = *parent;
+                }
+                word.reverse();
+
+                return Some(word);
+            }
+
+            for sym_idx in 0..self.symbols.len() {
+                let next = (self.next[pair.0][sym_idx], self.next[pair.1][sym_idx]);
+                let next = (next.0.min(next.1), next.0.max(next.1));
+
+                if let Entry::Vacant(entry) = parents.entry(next) {
+                    entry.insert(Some((pair, sym_idx)));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    // Eppstein's greedy algorithm: repeatedly merge two of the remaining states
+    // with a shortest word for that pair.
+    fn greedy(&self) -> Option<Vec<Sym>> {
+        let mut current: VecSet<usize> = (0..self.num_states()).collect();
+        let mut word = Vec::new();
+
+        while current.as_slice().len() > 1 {
+            let (p, q) = (current.as_slice()[0], current.as_slice()[1]);
+            for sym_idx in self.merging_word(p, q)? {
+                current = self.image(&current, sym_idx);
+                word.push(self.symbols[sym_idx].clone());
+            }
+        }
+
+        Some(word)
+    }
+
+    // Breadth first search over sets of states for a shortest word that takes
+    // the set of all states to a single state.
+    fn exact(&self) -> Option<Vec<Sym>> {
+        let all: VecSet<usize> = (0..self.num_states()).collect();
+        let mut parents: Parents<VecSet<usize>> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        parents.insert(all.clone(), None);
+        queue.push_back(all);
+
+        while let Some(current) = queue.pop_front() {
+            if current.as_slice().len() <= 1 {
+                let mut word = Vec::new();
+                let mut set = &current;
+                while let Some(Some((parent, sym_idx))) = parents.get(set) {
+                    word.push(self.symbols[*sym_idx].clone());
+                    set = parent;
+                }
+                word.reverse();
+
+                return Some(word);
+            }
+
+            for sym_idx in 0..self.symbols.len() {
+                let next = self.image(&current, sym_idx);
+
+                if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                    entry.insert(Some((current.clone(), sym_idx)));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<A, S> DFA<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone,
+{
+    fn table(&self) -> Table<A::Symbol> {
+        let symbols: Vec<A::Symbol> = self.storage.alphabet().values().collect();
+        let states = self.storage.all_states();
+        let indices: BTreeMap<S::State, usize> = states
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(idx, state)| (state, idx))
+            .collect();
+        let sink = states.len();
+        let mut needs_sink = false;
+
+        let mut next: Vec<Vec<usize>> = states
+            .iter()
+            .map(|state| {
+                symbols
+                    .iter()
+                    .map(
+                        |sym| match self.storage.transition(state.clone(), sym.clone()) {
+                            Some(target) => indices[&target],
+                            None => {
+                                needs_sink = true;
+                                sink
+                            }
+                        },
+                    )
+                    .collect()
+            })
+            .collect();
+
+        if needs_sink {
+            next.push(vec![sink; symbols.len()]);
+        }
+
+        Table { symbols, next }
+    }
+
+    /// Return a string that takes every state of this DFA to the same state, if
+    /// such a string exists.
+    ///
+    /// For automata with few states the string is a shortest one, found by an
+    /// exhaustive search. Larger automata use Eppstein's greedy algorithm,
+    /// which runs in polynomial time but may return a longer string.
+    ///
+    /// A missing transition is treated as a transition to an implicit sink
+    /// state, which must then be the state that every state is taken to.
+    pub fn synchronizing_word(&self) -> Option<Vec<A::Symbol>> {
+        let table = self.table();
+
+        if table.num_states() <= EXACT_SEARCH_LIMIT {
+            table.exact()
+        } else {
+            table.greedy()
+        }
+    }
+
+    /// Return a shortest string that takes every state of this DFA to the same
+    /// state, if such a string exists.
+    ///
+    /// The search explores sets of states, so it may take time exponential in
+    /// the number of states.
+    pub fn shortest_synchronizing_word(&self) -> Option<Vec<A::Symbol>> {
+        self.table().exact()
+    }
+
+    /// Return `true` if some string takes every state of this DFA to the same
+    /// state.
+    pub fn is_synchronizing(&self) -> bool {
+        // Every pair of states can be merged exactly when all states can be, so
+        // the polynomial greedy algorithm decides this.
+        self.table().greedy().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dfa::{DFABuilder, DFAStorage, DFA},
+        test_helper::{CONTAINS_EVEN_TRUES_DFA, CONTAINS_TWO_FALSE_DFA},
+        util::Range,
+    };
+
+    // The Černý automaton with `n` states, whose shortest synchronizing word
+    // has length `(n - 1)^2`. `a` rotates the states, `b` merges state 0 into
+    // state 1.
+    fn cerny(n: usize) -> DFA<Range<char>> {
+        let mut builder = DFABuilder::new('a'..='b');
+        let states: Vec<_> = (0..n).map(|_| builder.new_state()).collect();
+
+        for idx in 0..n {
+            builder
+                .transition(states[idx], 'a', states[(idx + 1) % n])
+                .unwrap();
+            builder
+                .transition(states[idx], 'b', states[if idx == 0 { 1 } else { idx }])
+                .unwrap();
+        }
+        builder.start_state(states[0]);
+
+        builder.build().unwrap()
+    }
+
+    fn final_states(dfa: &DFA<Range<char>>, word: &[char]) -> Vec<usize> {
+        let mut finals: Vec<usize> = dfa
+            .storage()
+            .all_states()
+            .into_iter()
+            .map(|state| {
+                word.iter().fold(state, |current, sym| {
+                    dfa.storage().transition(current, *sym).unwrap()
+                })
+            })
+            .collect();
+        finals.sort_unstable();
+        finals.dedup();
+
+        finals
+    }
+
+    #[test]
+    fn cerny_automata_synchronize() {
+        for n in 2..6 {
+            let dfa = cerny(n);
+
+            let shortest = dfa.shortest_synchronizing_word().unwrap();
+            assert_eq!(shortest.len(), (n - 1) * (n - 1));
+            assert_eq!(final_states(&dfa, &shortest).len(), 1);
+
+            let greedy = dfa.table().greedy().unwrap();
+            assert_eq!(final_states(&dfa, &greedy).len(), 1);
+            assert!(dfa.is_synchronizing());
+        }
+    }
+
+    #[test]
+    fn non_synchronizing() {
+        // A permutation automaton never merges states.
+        assert!(!CONTAINS_EVEN_TRUES_DFA.is_synchronizing());
+        assert!(CONTAINS_EVEN_TRUES_DFA.synchronizing_word().is_none());
+
+        // Every state is taken to the absorbing accept state by two `false`.
+        let word: Vec<bool> = CONTAINS_TWO_FALSE_DFA.synchronizing_word().unwrap();
+        assert_eq!(word, vec![false, false]);
+    }
+}
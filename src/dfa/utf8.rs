@@ -0,0 +1,341 @@
+use crate::{
+    collections::BTreeMap,
+    dfa::{DFABuilder, DFAStorage, DFA},
+    error::Error,
+    util::Range,
+};
+use alloc::{vec, vec::Vec};
+
+// The largest scalar value encoded with 1, 2 and 3 bytes.
+const ENCODING_LENGTH_ENDS: [u32; 3] = [0x7F, 0x7FF, 0xFFFF];
+
+// The scalar values below and above the surrogates.
+const SCALAR_RANGES: [(u32, u32); 2] = [(0, 0xD7FF), (0xE000, 0x10FFFF)];
+
+// The byte range of every continuation byte.
+const CONTINUATION: (u8, u8) = (0x80, 0xBF);
+
+// Split a range of scalar values, which must not contain surrogates, into
+// sequences of byte ranges. The UTF-8 encodings of the range are exactly the
+// byte strings matched by one of the sequences.
+//
+// Each sequence is some fixed bytes, then a range of bytes, then full ranges
+// of continuation bytes. Two sequences from disjoint ranges therefore never
+// have partially overlapping byte ranges after an equal prefix.
+fn utf8_sequences(start: u32, end: u32) -> Vec<Vec<(u8, u8)>> {
+    let mut sequences = Vec::new();
+    let mut stack = vec![(start, end)];
+
+    'split: while let Some((start, end)) = stack.pop() {
+        // Both ends must be encoded with the same number of bytes.
+        for max in ENCODING_LENGTH_ENDS {
+            if start <= max && max < end {
+                stack.push((max + 1, end));
+                stack.push((start, max));
+                continue 'split;
+            }
+        }
+
+        // Below the last byte, the range must either share the bytes of both
+        // ends or cover every continuation byte.
+        for idx in 1..4 {
+            let max = (1 << (6 * idx)) - 1;
+            if start & !max != end & !max {
+                if start & max != 0 {
+                    stack.push(((start | max) + 1, end));
+                    stack.push((start, start | max));
+                    continue 'split;
+                }
+                if end & max != max {
+                    stack.push((end & !max, end));
+                    stack.push((start, (end & !max) - 1));
+                    continue 'split;
+                }
+            }
+        }
+
+        let (mut start_buf, mut end_buf) = ([0; 4], [0; 4]);
+        let start = encode(start, &mut start_buf);
+        let end = encode(end, &mut end_buf);
+        sequences.push(start.iter().copied().zip(end.iter().copied()).collect());
+    }
+
+    sequences
+}
+
+fn encode(scalar: u32, buf: &mut [u8; 4]) -> &[u8] {
+    char::from_u32(scalar)
+        .expect("ranges do not contain surrogates")
+        .encode_utf8(buf)
+        .as_bytes()
+}
+
+// The transitions of each state, as runs `(start, end, to)` of consecutive
+// scalar values with the same target.
+type Runs<State> = BTreeMap<State, Vec<(u32, u32, State)>>;
+
+// A state of the byte DFA between two characters, or in the middle of one.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Node<State> {
+    // A state of the character DFA.
+    Char(State),
+    // After reading the given byte ranges from a state.
+    Prefix(State, Vec<(u8, u8)>),
+    // The given number of continuation bytes are left before the state.
+    Tail(State, usize),
+}
+
+impl<S> DFA<Range<char>, S>
+where
+    S: DFAStorage<Range<char>>,
+    S::State: Ord,
+{
+    /// Construct a new DFA over bytes that accepts the UTF-8 encodings of the
+    /// strings accepted by this DFA, so it can run directly on `&[u8]`.
+    ///
+    /// The states of this DFA are kept, and new states are added for reading
+    /// each character one byte at a time. Invalid UTF-8 is rejected by a
+    /// missing transition, also after an accepting dead state, which is an
+    /// ordinary state of the new DFA. The new DFA can be stored in a
+    /// `BinaryDFAStorage`, which has a dense transition table.
+    pub fn to_utf8<S2>(&self) -> Result<DFA<Range<u8>, S2>, Error>
+    where
+        S2: DFAStorage<Range<u8>>,
+        S2::State: Ord,
+    {
+        let bytes = Range::NonEmpty {
+            start: u8::MIN,
+            end: u8::MAX,
+        };
+        let mut builder = DFABuilder::new_with_storage(S2::from_alphabet(bytes));
+        let mut nodes: BTreeMap<Node<S::State>, S2::State> = BTreeMap::new();
+
+        for state in self.storage.all_states() {
+            let new_state = builder.new_state();
+            if let Some(label) = self.labels.get(&state) {
+                builder.labels.insert(new_state.clone(), label.clone());
+            }
+            nodes.insert(Node::Char(state), new_state);
+        }
+
+        let mut runs: Runs<S::State> = BTreeMap::new();
+        let mut transitions = self.storage.all_transitions();
+        transitions.sort_by(|(from, sym, _), (other_from, other_sym, _)| {
+            (from, sym).cmp(&(other_from, other_sym))
+        });
+        for (from, sym, to) in transitions {
+            // The DFA stops reading in its dead state.
+            if self.dead.as_ref() == Some(&from) {
+                continue;
+            }
+
+            let state_runs = runs.entry(from).or_default();
+            let scalar = u32::from(sym);
+            match state_runs.last_mut() {
+                Some((_, end, target)) if *end + 1 == scalar && *target == to => *end = scalar,
+                _ => state_runs.push((scalar, scalar, to)),
+            }
+        }
+
+        // Instead, it loops on every character, so that only valid UTF-8 is
+        // read after it.
+        if let Some(dead) = self.dead.as_ref() {
+            let loops = SCALAR_RANGES
+                .iter()
+                .map(|(start, end)| (*start, *end, dead.clone()))
+                .collect();
+            runs.insert(dead.clone(), loops);
+        }
+
+        for (from, state_runs) in runs {
+            for (start, end, to) in state_runs {
+                for sequence in utf8_sequences(start, end) {
+                    let mut current = nodes[&Node::Char(from.clone())].clone();
+
+                    for (idx, range) in sequence.iter().enumerate() {
+                        let rest = &sequence[idx + 1..];
+                        let node = if rest.is_empty() {
+                            Node::Char(to.clone())
+                        } else if rest.iter().all(|range| *range == CONTINUATION) {
+                            Node::Tail(to.clone(), rest.len())
+                        } else {
+                            Node::Prefix(from.clone(), sequence[..=idx].to_vec())
+                        };
+                        let next = nodes
+                            .entry(node)
+                            .or_insert_with(|| builder.new_state())
+                            .clone();
+
+                        for byte in range.0..=range.1 {
+                            builder.add_transition(current.clone(), byte, next.clone());
+                        }
+                        current = next;
+                    }
+                }
+            }
+        }
+
+        // An accepting dead state rejects invalid UTF-8 after it, so it is
+        // only a dead state of the new DFA if it rejects.
+        let dead = self
+            .dead
+            .as_ref()
+            .filter(|dead| !self.accept.contains(dead))
+            .map(|dead| nodes[&Node::Char(dead.clone())].clone());
+
+        builder
+            .start_state(nodes[&Node::Char(self.start.clone())].clone())
+            .dead_state(dead)
+            .accept_states(
+                self.accept
+                    .iter()
+                    .map(|state| nodes[&Node::Char(state.clone())].clone()),
+            );
+
+        Ok(builder.build_unchecked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alphabet::IntoAlphabet, dfa::BinaryDFAStorage};
+    use alloc::string::String;
+    use proptest::{prop_assert_eq, proptest};
+
+    // Check that the sequences match exactly the encodings of the range.
+    fn check_sequences(start: char, end: char) {
+        let sequences = utf8_sequences(start.into(), end.into());
+        let matches = |bytes: &[u8]| {
+            sequences.iter().any(|sequence| {
+                sequence.len() == bytes.len()
+                    && sequence
+                        .iter()
+                        .zip(bytes)
+                        .all(|((lo, hi), byte)| lo <= byte && byte <= hi)
+            })
+        };
+
+        for scalar in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(scalar) {
+                let mut buf = [0; 4];
+                let inside = start <= c && c <= end;
+                assert_eq!(
+                    matches(c.encode_utf8(&mut buf).as_bytes()),
+                    inside,
+                    "{:?}",
+                    c
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sequences_match_encodings() {
+        check_sequences('\0', '\u{D7FF}');
+        check_sequences('\u{E000}', char::MAX);
+        check_sequences('\u{7E}', '\u{801}');
+        check_sequences('\u{FFF}', '\u{10400}');
+    }
+
+    // Strings of `a`, `é`, `€` and `😀`, ending in a character other than `a`.
+    fn not_ending_in_a() -> DFA<Range<char>> {
+        let mut builder = DFABuilder::new(('a'..='\u{1F600}').into_alphabet());
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        let dead = builder.new_state();
+
+        for c in builder.alphabet().clone() {
+            let (from_q0, from_q1) = match c {
+                'a' => (q0, q0),
+                'é' | '€' | '😀' => (q1, q1),
+                _ => (dead, dead),
+            };
+            builder.transition(q0, c, from_q0).unwrap();
+            builder.transition(q1, c, from_q1).unwrap();
+            builder.transition(dead, c, dead).unwrap();
+        }
+        builder
+            .start_state(q0)
+            .dead_state(Some(dead))
+            .accept_states([q1]);
+
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn matches_encoded_strings() {
+        let chars = not_ending_in_a();
+        let bytes: DFA<Range<u8>> = chars.to_utf8().unwrap();
+
+        proptest!(|(string in "[aé€😀b]*")| {
+            prop_assert_eq!(bytes.accept(string.bytes()), chars.accept(string.chars()));
+        });
+
+        assert!(!bytes.accept(b"a\xC3".iter().copied()));
+        assert!(!bytes.accept(b"\xE9".iter().copied()));
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        let mut builder = DFABuilder::new('\0'..=char::MAX);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        for c in builder.alphabet().clone() {
+            builder.transition(q0, c, q1).unwrap();
+        }
+        builder.start_state(q0).accept_states([q1]);
+        let any_char: DFA<Range<u8>> = builder.build().unwrap().to_utf8().unwrap();
+
+        proptest!(|(c: char)| {
+            let mut buf = [0; 4];
+            prop_assert_eq!(any_char.accept(c.encode_utf8(&mut buf).bytes()), true);
+        });
+
+        // Overlong, surrogate, out of range and truncated encodings.
+        for bytes in [
+            &b"\xC0\x80"[..],
+            b"\xED\xA0\x80",
+            b"\xF4\x90\x80\x80",
+            b"\xF0\x9F\x98",
+            b"\x80",
+        ] {
+            assert!(!any_char.accept(bytes.iter().copied()), "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn accepting_dead_state_reads_valid_utf8() {
+        // Strings that contain an `a`.
+        let mut builder = DFABuilder::new(('a'..='b').into_alphabet());
+        let q0 = builder.new_state();
+        let dead = builder.new_state();
+        builder
+            .transitions([(q0, 'a', dead), (q0, 'b', q0)])
+            .unwrap();
+        builder
+            .start_state(q0)
+            .dead_state(Some(dead))
+            .accept_states([dead]);
+        let chars = builder.build().unwrap();
+        let bytes: DFA<Range<u8>> = chars.to_utf8().unwrap();
+
+        proptest!(|(string in "[ab]*a\\PC*")| {
+            prop_assert_eq!(bytes.accept(string.bytes()), true);
+        });
+        assert!(!bytes.accept("bb".bytes()));
+        for invalid in [&b"a\xFF"[..], b"ba\xC3", b"a\xED\xA0\x80", b"a\x80"] {
+            assert!(!bytes.accept(invalid.iter().copied()), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn into_dense_storage() {
+        let chars = not_ending_in_a();
+        let bytes: DFA<Range<u8>, BinaryDFAStorage<Range<u8>>> = chars.to_utf8().unwrap();
+
+        let string = String::from("aé€😀");
+        assert!(bytes.accept(string.bytes()));
+        assert!(!bytes.accept("😀a".bytes()));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::{BTreeSet, VecDeque},
+    dfa::{DFABuilder, DFAStorage, DFA},
+    error::Error,
+};
+use alloc::{format, vec::Vec};
+use core::fmt::Debug;
+
+// A transition that replaced an earlier one, as `(from, sym, previous, to)`.
+pub(super) type Overwrite<State, Sym> = (State, Sym, State, State);
+
+/// Problems found in a `DFABuilder` by `DFABuilder::validate`.
+///
+/// None of these stop `DFABuilder::build` from producing a DFA, but each
+/// usually means the transitions were not what was intended.
+/// `DFABuilder::build_strict` rejects a builder with any of them.
+#[derive(Debug, Clone)]
+pub struct ValidationReport<State, Sym> {
+    overwritten: Vec<Overwrite<State, Sym>>,
+    missing: Vec<(State, Sym)>,
+    unreachable: Vec<State>,
+    unreachable_accept: Vec<State>,
+    dead_exits: Vec<(Sym, State)>,
+}
+
+impl<State, Sym> ValidationReport<State, Sym> {
+    /// Return `true` if no problems were found.
+    pub fn is_valid(&self) -> bool {
+        self.overwritten.is_empty()
+            && self.missing.is_empty()
+            && self.unreachable.is_empty()
+            && self.dead_exits.is_empty()
+    }
+
+    /// Transitions that replaced an earlier transition from the same state on
+    /// the same symbol, as `(from, sym, previous, to)`.
+    pub fn overwritten_transitions(&self) -> &[(State, Sym, State, State)] {
+        &self.overwritten
+    }
+
+    /// Pairs of a state and a symbol with no transition. The dead state is
+    /// not checked, since a DFA stops there.
+    pub fn missing_transitions(&self) -> &[(State, Sym)] {
+        &self.missing
+    }
+
+    /// States that cannot be reached from the start state. Every state is
+    /// unreachable when there is no start state.
+    pub fn unreachable_states(&self) -> &[State] {
+        &self.unreachable
+    }
+
+    /// Accept states that cannot be reached from the start state, so can
+    /// never cause a string to be accepted.
+    pub fn unreachable_accept_states(&self) -> &[State] {
+        &self.unreachable_accept
+    }
+
+    /// Transitions from the dead state to another state, as `(sym, to)`.
+    pub fn dead_state_exits(&self) -> &[(Sym, State)] {
+        &self.dead_exits
+    }
+}
+
+impl<A, S> DFABuilder<A, S>
+where
+    S: DFAStorage<A>,
+    S::State: Ord,
+    A: Alphabet,
+    A::Symbol: Clone,
+{
+    /// Check the transitions of this builder for overwritten and missing
+    /// transitions, states that are unreachable from the start state, and a
+    /// dead state that can be left.
+    pub fn validate(&self) -> ValidationReport<S::State, A::Symbol> {
+        let states = self.storage.all_states();
+        let symbols: Vec<A::Symbol> = self.alphabet().values().collect();
+
+        let mut reachable = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        if let Some(start) = self.start.iter().find(|s| self.storage.contains_state(s)) {
+            reachable.insert(start.clone());
+            queue.push_back(start.clone());
+        }
+        while let Some(state) = queue.pop_front() {
+            if self.dead.as_ref() == Some(&state) {
+                continue;
+            }
+            for sym in &symbols {
+                if let Some(next) = self.storage.transition(state.clone(), sym.clone()) {
+                    if reachable.insert(next.clone()) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        let mut missing = Vec::new();
+        let mut dead_exits = Vec::new();
+        for state in &states {
+            let is_dead = self.dead.as_ref() == Some(state);
+            for sym in &symbols {
+                match self.storage.transition(state.clone(), sym.clone()) {
+                    None if !is_dead => missing.push((state.clone(), sym.clone())),
+                    Some(next) if is_dead && next != *state => {
+                        dead_exits.push((sym.clone(), next));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let unreachable: Vec<S::State> = states
+            .into_iter()
+            .filter(|state| !reachable.contains(state))
+            .collect();
+        let unreachable_accept = self
+            .accept
+            .iter()
+            .filter(|state| !reachable.contains(*state))
+            .cloned()
+            .collect();
+
+        ValidationReport {
+            overwritten: self.overwritten.clone(),
+            missing,
+            unreachable,
+            unreachable_accept,
+            dead_exits,
+        }
+    }
+
+    /// Build the DFA, rejecting any of the problems found by `validate`.
+    ///
+    /// # Error
+    ///
+    /// See the Error documentation of `build`. This function will also error
+    /// with `Error::Nondeterministic` if a transition was overwritten,
+    /// `Error::IncompleteTransitions` if a transition is missing,
+    /// `Error::UnreachableState` if a state is unreachable, and
+    /// `Error::DeadStateNotSink` if the dead state can be left.
+    pub fn build_strict(self) -> Result<DFA<A, S>, Error>
+    where
+        S::State: Debug,
+        A::Symbol: Debug,
+    {
+        let report = self.validate();
+        let dfa = self.build()?;
+
+        if let Some((from, sym, ..)) = report.overwritten.first() {
+            return Err(Error::nondeterministic(from, sym, None));
+        }
+        if let Some((state, sym)) = report.missing.first() {
+            return Err(Error::incomplete_transitions("build_strict", state, sym));
+        }
+        if let Some(state) = report.unreachable.first() {
+            return Err(Error::UnreachableState {
+                state: format!("{:?}", state),
+            });
+        }
+        if let Some((sym, _)) = report.dead_exits.first() {
+            return Err(Error::DeadStateNotSink {
+                state: format!("{:?}", dfa.dead.as_ref().expect("dead state has exits")),
+                symbol: format!("{:?}", sym),
+            });
+        }
+
+        Ok(dfa)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alphabet::Boolean, test_helper::CONTAINS_TWO_FALSE_DFA};
+
+    #[test]
+    fn complete_dfa_is_valid() {
+        let builder = CONTAINS_TWO_FALSE_DFA.clone().into_builder();
+
+        assert!(builder.validate().is_valid());
+        assert!(builder.build_strict().is_ok());
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        let q2 = builder.new_state();
+        let dead = builder.new_state();
+
+        builder
+            .transitions(vec![
+                (q0, false, q1),
+                (q0, false, dead),
+                (q0, true, q0),
+                (dead, false, dead),
+                (dead, true, q0),
+                (q2, false, q2),
+            ])
+            .unwrap();
+        builder
+            .start_state(q0)
+            .dead_state(Some(dead))
+            .accept_states(vec![q1, q2]);
+
+        let report = builder.validate();
+        assert!(!report.is_valid());
+        assert_eq!(report.overwritten_transitions(), &[(q0, false, q1, dead)]);
+        assert_eq!(
+            report.missing_transitions(),
+            &[(q1, false), (q1, true), (q2, true)]
+        );
+        assert_eq!(report.unreachable_states(), &[q1, q2]);
+        assert_eq!(report.unreachable_accept_states(), &[q1, q2]);
+        assert_eq!(report.dead_state_exits(), &[(true, q0)]);
+
+        assert!(builder.clone().build().is_ok());
+        assert!(matches!(
+            builder.build_strict(),
+            Err(Error::Nondeterministic { ref state, line: None, .. }) if state == "0"
+        ));
+    }
+
+    #[test]
+    fn forgets_removed_overwrites() {
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        builder
+            .transitions(vec![(q0, false, q0), (q0, false, q1), (q0, true, q0)])
+            .unwrap();
+        builder.start_state(q0);
+        assert_eq!(
+            builder.validate().overwritten_transitions(),
+            &[(q0, false, q0, q1)]
+        );
+
+        assert_eq!(builder.remove_transition(q0, false).unwrap(), Some(q1));
+        assert!(builder.validate().overwritten_transitions().is_empty());
+
+        builder.transition(q0, false, q1).unwrap();
+        assert!(builder.validate().overwritten_transitions().is_empty());
+
+        builder.transition(q0, true, q1).unwrap();
+        builder.remove_state(q1).unwrap();
+        builder.transition(q0, false, q0).unwrap();
+        builder.transition(q0, true, q0).unwrap();
+        assert!(builder.validate().overwritten_transitions().is_empty());
+        assert!(builder.build_strict().is_ok());
+    }
+
+    #[test]
+    fn strict_build_errors() {
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let q1 = builder.new_state();
+        builder.transitions(vec![(q0, false, q0)]).unwrap();
+        builder.start_state(q0);
+
+        assert!(matches!(
+            builder.clone().build_strict(),
+            Err(Error::IncompleteTransitions { ref symbol, .. }) if symbol == "true"
+        ));
+
+        builder
+            .transitions(vec![(q0, true, q0), (q1, false, q1), (q1, true, q1)])
+            .unwrap();
+        assert!(matches!(
+            builder.clone().build_strict(),
+            Err(Error::UnreachableState { ref state }) if state == "1"
+        ));
+
+        let mut builder = DFABuilder::new(Boolean);
+        let q0 = builder.new_state();
+        let dead = builder.new_state();
+        builder
+            .transitions(vec![
+                (q0, false, dead),
+                (q0, true, dead),
+                (dead, false, dead),
+                (dead, true, q0),
+            ])
+            .unwrap();
+        builder.start_state(q0).dead_state(Some(dead));
+        assert!(matches!(
+            builder.build_strict(),
+            Err(Error::DeadStateNotSink { ref state, ref symbol }) if state == "1" && symbol == "true"
+        ));
+    }
+}
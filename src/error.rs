@@ -1,33 +1,250 @@
-use core::fmt;
+use alloc::{boxed::Box, format, string::String};
+use core::fmt::{self, Debug};
 
 /// Errors from `regular` operations.
+///
+/// States and symbols are generic, so errors carry their `Debug`
+/// representation.
 #[derive(Debug, Clone)]
 pub enum Error {
     /// Start state was not specified.
     MissingStartState,
     /// State specified was not valid for this automaton.
-    InvalidState,
+    InvalidState {
+        /// The offending state.
+        state: String,
+    },
     /// Symbol not found in alphabet.
-    SymbolNotInAlphabet,
+    SymbolNotInAlphabet {
+        /// The offending symbol.
+        symbol: String,
+    },
     /// State not found.
     StateNotFound,
     /// Attempted to perform operation with two different alphabets.
-    OperationWithNonEqualAlphabets,
+    OperationWithNonEqualAlphabets {
+        /// The name of the operation.
+        operation: &'static str,
+    },
+    /// Attempted to perform an operation on several automata with none.
+    NoOperands {
+        /// The name of the operation.
+        operation: &'static str,
+    },
+    /// An operation required a transition that was missing.
+    IncompleteTransitions {
+        /// The name of the operation.
+        operation: &'static str,
+        /// The state without a transition.
+        state: String,
+        /// The symbol without a transition.
+        symbol: String,
+    },
+    /// There was more than one transition from a state on the same symbol.
+    Nondeterministic {
+        /// The state with several transitions.
+        state: String,
+        /// The symbol of the transitions.
+        symbol: String,
+        /// The line of the repeated transition, if it was read from text.
+        line: Option<usize>,
+    },
+    /// A state could not be reached from the start state.
+    UnreachableState {
+        /// The unreachable state.
+        state: String,
+    },
+    /// The dead state had a transition to another state.
+    DeadStateNotSink {
+        /// The dead state.
+        state: String,
+        /// The symbol of the transition leaving the dead state.
+        symbol: String,
+    },
+    /// A transition in a batch of transitions was invalid.
+    InTransitionBatch {
+        /// The index of the transition in the batch, starting from 0.
+        index: usize,
+        /// The error for that transition.
+        source: Box<Error>,
+    },
+    /// The storage of an automaton does not support an operation.
+    UnsupportedOperation {
+        /// The name of the operation.
+        operation: &'static str,
+    },
+    /// A formula referred to a proposition outside of the propositions it was
+    /// compiled with.
+    InvalidProposition {
+        /// The index of the proposition.
+        index: u32,
+        /// The number of propositions.
+        num_props: u32,
+    },
+    /// A formula was compiled with more propositions than are supported.
+    TooManyPropositions {
+        /// The number of propositions requested.
+        num_props: u32,
+        /// The largest supported number of propositions.
+        max: u32,
+    },
+    /// Examples or oracle answers given to a learning algorithm contradicted
+    /// each other.
+    InconsistentExamples,
+    /// Binary DFA data was malformed, or a DFA could not be represented in the
+    /// binary format.
+    InvalidBinaryFormat {
+        /// What was wrong with the data.
+        reason: &'static str,
+    },
+    /// A line of a text automaton or symbol table could not be parsed.
+    MalformedLine {
+        /// The line number, starting from 1.
+        line: usize,
+    },
+    /// A label in a text automaton had no symbol in the symbol table, or a
+    /// symbol had no label when writing one.
+    UnknownSymbol {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The label, or the debug representation of the symbol.
+        label: String,
+    },
 }
 
-impl std::error::Error for Error {}
+impl Error {
+    pub(crate) fn invalid_state<State: Debug>(state: &State) -> Self {
+        Error::InvalidState {
+            state: format!("{:?}", state),
+        }
+    }
+
+    pub(crate) fn symbol_not_in_alphabet<Sym: Debug>(symbol: &Sym) -> Self {
+        Error::SymbolNotInAlphabet {
+            symbol: format!("{:?}", symbol),
+        }
+    }
+
+    pub(crate) fn incomplete_transitions<State: Debug, Sym: Debug>(
+        operation: &'static str,
+        state: &State,
+        symbol: &Sym,
+    ) -> Self {
+        Error::IncompleteTransitions {
+            operation,
+            state: format!("{:?}", state),
+            symbol: format!("{:?}", symbol),
+        }
+    }
+
+    pub(crate) fn nondeterministic<State: Debug, Sym: Debug>(
+        state: &State,
+        symbol: &Sym,
+        line: Option<usize>,
+    ) -> Self {
+        Error::Nondeterministic {
+            state: format!("{:?}", state),
+            symbol: format!("{:?}", symbol),
+            line,
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::InTransitionBatch { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::MissingStartState => write!(f, "Start state was not specified."),
-            Error::InvalidState => write!(f, "State specified was not valid for this automaton."),
-            Error::SymbolNotInAlphabet => write!(f, "Symbol not found in alphabet."),
-            Error::OperationWithNonEqualAlphabets => write!(
+            Error::InvalidState { state } => {
+                write!(f, "State {} was not valid for this automaton.", state)
+            }
+            Error::SymbolNotInAlphabet { symbol } => {
+                write!(f, "Symbol {} not found in alphabet.", symbol)
+            }
+            Error::OperationWithNonEqualAlphabets { operation } => write!(
+                f,
+                "Attempted to perform {} with two different alphabets.",
+                operation
+            ),
+            Error::NoOperands { operation } => {
+                write!(
+                    f,
+                    "Attempted to perform {} without any automata.",
+                    operation
+                )
+            }
+            Error::IncompleteTransitions {
+                operation,
+                state,
+                symbol,
+            } => write!(
+                f,
+                "Attempted to perform {} without a transition from state {} on symbol {}.",
+                operation, state, symbol
+            ),
+            Error::Nondeterministic {
+                state,
+                symbol,
+                line: Some(line),
+            } => write!(
+                f,
+                "Transition on line {} repeats state {} and symbol {}.",
+                line, state, symbol
+            ),
+            Error::Nondeterministic {
+                state,
+                symbol,
+                line: None,
+            } => write!(
+                f,
+                "Multiple transitions from state {} on symbol {}.",
+                state, symbol
+            ),
+            Error::UnreachableState { state } => {
+                write!(f, "State {} is unreachable from the start state.", state)
+            }
+            Error::DeadStateNotSink { state, symbol } => write!(
                 f,
-                "Attempted to perform operation with two different alphabets."
+                "Dead state {} has a transition on symbol {} to another state.",
+                state, symbol
             ),
+            Error::InTransitionBatch { index, source } => {
+                write!(
+                    f,
+                    "Transition {} of the batch was invalid: {}",
+                    index, source
+                )
+            }
             Error::StateNotFound => write!(f, "State not found."),
+            Error::UnsupportedOperation { operation } => {
+                write!(f, "The storage does not support {}.", operation)
+            }
+            Error::InvalidProposition { index, num_props } => write!(
+                f,
+                "Proposition {} is not one of the {} propositions.",
+                index, num_props
+            ),
+            Error::TooManyPropositions { num_props, max } => write!(
+                f,
+                "{} propositions were requested, but at most {} are supported.",
+                num_props, max
+            ),
+            Error::InconsistentExamples => write!(f, "Examples contradicted each other."),
+            Error::InvalidBinaryFormat { reason } => {
+                write!(f, "Binary DFA data was malformed: {}.", reason)
+            }
+            Error::MalformedLine { line } => write!(f, "Line {} is malformed.", line),
+            Error::UnknownSymbol { line, label } => {
+                write!(f, "Unknown symbol `{}` on line {}.", label, line)
+            }
         }
     }
 }
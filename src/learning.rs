@@ -0,0 +1,152 @@
+#[cfg(feature = "rand")]
+use crate::util::uniform_below;
+use crate::{
+    alphabet::Alphabet,
+    dfa::{DFAStorage, DFA},
+    error::Error,
+};
+use alloc::vec::Vec;
+#[cfg(feature = "rand")]
+use rand_core::Rng;
+
+mod lstar;
+mod rpni;
+
+pub use lstar::LStar;
+pub use rpni::{MergeStrategy, Rpni};
+
+/// An oracle that can decide whether a string belongs to the language being
+/// learned.
+pub trait MembershipOracle<Sym> {
+    /// Return `true` if the given string is a member of the language.
+    fn is_member(&mut self, string: &[Sym]) -> bool;
+}
+
+impl<Sym, F> MembershipOracle<Sym> for F
+where
+    F: FnMut(&[Sym]) -> bool,
+{
+    fn is_member(&mut self, string: &[Sym]) -> bool {
+        self(string)
+    }
+}
+
+/// An oracle that can decide whether a hypothesis DFA accepts the language
+/// being learned.
+pub trait EquivalenceOracle<A: Alphabet, S: DFAStorage<A>> {
+    /// Return a string that the hypothesis classifies incorrectly, or `None`
+    /// if the hypothesis is correct.
+    ///
+    /// # Error
+    ///
+    /// An oracle should error if it cannot compare against the hypothesis,
+    /// for example because the alphabets differ. The error stops learning.
+    fn counterexample(&mut self, hypothesis: &DFA<A, S>) -> Result<Option<Vec<A::Symbol>>, Error>;
+}
+
+/// An equivalence oracle that compares the hypothesis against a known DFA.
+///
+/// The counterexamples returned are the shortest possible.
+#[derive(Clone)]
+pub struct DFAEquivalenceOracle<'a, A: Alphabet, S: DFAStorage<A>> {
+    target: &'a DFA<A, S>,
+}
+
+impl<'a, A, S> DFAEquivalenceOracle<'a, A, S>
+where
+    A: Alphabet,
+    S: DFAStorage<A>,
+{
+    /// Create an equivalence oracle for the language of the given DFA.
+    pub fn new(target: &'a DFA<A, S>) -> Self {
+        DFAEquivalenceOracle { target }
+    }
+}
+
+impl<'a, A, S, S2> EquivalenceOracle<A, S2> for DFAEquivalenceOracle<'a, A, S>
+where
+    A: Alphabet + Clone + PartialEq,
+    A::Symbol: Clone,
+    S: DFAStorage<A>,
+    S::State: Ord,
+    S2: DFAStorage<A>,
+    S2::State: Ord,
+{
+    /// # Error
+    ///
+    /// This function will error with `Error::OperationWithNonEqualAlphabets`
+    /// if the hypothesis has a different alphabet from the target.
+    fn counterexample(&mut self, hypothesis: &DFA<A, S2>) -> Result<Option<Vec<A::Symbol>>, Error> {
+        self.target.distinguishing_string(hypothesis)
+    }
+}
+
+/// An equivalence oracle that tests the hypothesis against a membership
+/// oracle on randomly sampled strings.
+///
+/// This oracle can only ever approximate equivalence: a hypothesis that
+/// survives every sample may still be incorrect. It requires the `rand`
+/// feature.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone)]
+pub struct RandomSamplingOracle<M, R> {
+    membership: M,
+    rng: R,
+    num_samples: usize,
+    max_len: usize,
+}
+
+#[cfg(feature = "rand")]
+impl<M, R> RandomSamplingOracle<M, R>
+where
+    R: Rng,
+{
+    /// Create an oracle that checks `num_samples` strings, each with a length
+    /// chosen uniformly from `0..=max_len`.
+    pub fn new(membership: M, rng: R, num_samples: usize, max_len: usize) -> Self {
+        RandomSamplingOracle {
+            membership,
+            rng,
+            num_samples,
+            max_len,
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<A, S, M, R> EquivalenceOracle<A, S> for RandomSamplingOracle<M, R>
+where
+    A: Alphabet,
+    A::Symbol: Clone,
+    S: DFAStorage<A>,
+    S::State: Ord,
+    M: MembershipOracle<A::Symbol>,
+    R: Rng,
+{
+    fn counterexample(&mut self, hypothesis: &DFA<A, S>) -> Result<Option<Vec<A::Symbol>>, Error> {
+        // The empty string is the only string over an empty alphabet, so it is
+        // always checked.
+        let empty = Vec::new();
+        if hypothesis.accept(empty.iter().cloned()) != self.membership.is_member(&empty) {
+            return Ok(Some(empty));
+        }
+
+        let symbols: Vec<_> = hypothesis.storage().alphabet().values().collect();
+        if symbols.is_empty() {
+            return Ok(None);
+        }
+
+        for _ in 0..self.num_samples {
+            let len = uniform_below(&mut self.rng, self.max_len + 1);
+            let string: Vec<_> = (0..len)
+                .map(|_| symbols[uniform_below(&mut self.rng, symbols.len())].clone())
+                .collect();
+
+            if hypothesis.accept(string.iter().cloned()) != self.membership.is_member(&string) {
+                return Ok(Some(string));
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::HashMap,
+    dfa::{DFABuilder, DFAStorage, DFA},
+    error::Error,
+    learning::{EquivalenceOracle, MembershipOracle},
+};
+use alloc::{vec, vec::Vec};
+use core::hash::Hash;
+
+/// Angluin's L* algorithm for actively learning a DFA from a membership
+/// oracle and an equivalence oracle.
+///
+/// Counterexamples are processed as described by Maler and Pnueli, by adding
+/// every suffix of the counterexample to the set of experiments. This keeps
+/// the observation table consistent, so only closedness has to be restored
+/// between hypotheses.
+#[derive(Debug)]
+pub struct LStar<A: Alphabet, M, E> {
+    alphabet: A,
+    membership: M,
+    equivalence: E,
+    cache: HashMap<Vec<A::Symbol>, bool>,
+}
+
+impl<A, M, E> LStar<A, M, E>
+where
+    A: Alphabet + Clone,
+    A::Symbol: Clone + Eq + Hash,
+    M: MembershipOracle<A::Symbol>,
+{
+    /// Create a new learner for a language over the given alphabet.
+    pub fn new(alphabet: A, membership: M, equivalence: E) -> Self {
+        LStar {
+            alphabet,
+            membership,
+            equivalence,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Return the number of distinct membership queries asked so far.
+    pub fn num_membership_queries(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Learn a DFA for the language, returning the first hypothesis that the
+    /// equivalence oracle accepts.
+    ///
+    /// The result is a minimal DFA for the language, though without a dead
+    /// state marked.
+    ///
+    /// # Error
+    ///
+    /// This function will error with any error from the equivalence oracle,
+    /// and with `Error::InconsistentExamples` if the oracle returns a
+    /// counterexample that is already accounted for, which means it disagrees
+    /// with the membership oracle.
+    pub fn learn<S>(&mut self) -> Result<DFA<A, S>, Error>
+    where
+        S: DFAStorage<A>,
+        S::State: Ord,
+        E: EquivalenceOracle<A, S>,
+    {
+        let symbols: Vec<A::Symbol> = self.alphabet.values().collect();
+        // The access strings, each of which has a distinct row.
+        let mut prefixes: Vec<Vec<A::Symbol>> = vec![Vec::new()];
+        // The experiments, of which the first is always the empty string.
+        let mut suffixes: Vec<Vec<A::Symbol>> = vec![Vec::new()];
+
+        loop {
+            self.close(&symbols, &mut prefixes, &suffixes);
+
+            let hypothesis = self.hypothesis::<S>(&symbols, &prefixes, &suffixes)?;
+            let counterexample = match self.equivalence.counterexample(&hypothesis)? {
+                Some(counterexample) => counterexample,
+                None => return Ok(hypothesis),
+            };
+
+            let mut progressed = false;
+            for start in 0..=counterexample.len() {
+                let suffix = counterexample[start..].to_vec();
+                if !suffixes.contains(&suffix) {
+                    suffixes.push(suffix);
+                    progressed = true;
+                }
+            }
+
+            // An oracle that repeats a counterexample already accounted for is
+            // not consistent with the membership oracle.
+            if !progressed {
+                return Err(Error::InconsistentExamples);
+            }
+        }
+    }
+
+    // Extend the access strings until every one step extension has the same
+    // row as some access string.
+    fn close(
+        &mut self,
+        symbols: &[A::Symbol],
+        prefixes: &mut Vec<Vec<A::Symbol>>,
+        suffixes: &[Vec<A::Symbol>],
+    ) {
+        let mut idx = 0;
+        while idx < prefixes.len() {
+            for sym in symbols {
+                let mut extension = prefixes[idx].clone();
+                extension.push(sym.clone());
+
+                let row = self.row(&extension, suffixes);
+                let known = prefixes
+                    .iter()
+                    .any(|prefix| self.row(prefix, suffixes) == row);
+                if !known {
+                    prefixes.push(extension);
+                }
+            }
+
+            idx += 1;
+        }
+    }
+
+    fn hypothesis<S>(
+        &mut self,
+        symbols: &[A::Symbol],
+        prefixes: &[Vec<A::Symbol>],
+        suffixes: &[Vec<A::Symbol>],
+    ) -> Result<DFA<A, S>, Error>
+    where
+        S: DFAStorage<A>,
+        S::State: Ord,
+    {
+        let mut builder = DFABuilder::new_with_storage(S::from_alphabet(self.alphabet.clone()));
+        let rows: Vec<Vec<bool>> = prefixes
+            .iter()
+            .map(|prefix| self.row(prefix, suffixes))
+            .collect();
+        let states: Vec<S::State> = prefixes.iter().map(|_| builder.new_state()).collect();
+
+        for (idx, prefix) in prefixes.iter().enumerate() {
+            for sym in symbols {
+                let mut extension = prefix.clone();
+                extension.push(sym.clone());
+
+                let row = self.row(&extension, suffixes);
+                let target = rows
+                    .iter()
+                    .position(|other| *other == row)
+                    .ok_or(Error::StateNotFound)?;
+
+                builder.add_transition(states[idx].clone(), sym.clone(), states[target].clone());
+            }
+        }
+
+        let accept = rows
+            .iter()
+            .zip(&states)
+            .filter(|(row, _)| row[0])
+            .map(|(_, state)| state.clone());
+
+        builder.start_state(states[0].clone()).accept_states(accept);
+
+        Ok(builder.build_unchecked())
+    }
+
+    fn row(&mut self, prefix: &[A::Symbol], suffixes: &[Vec<A::Symbol>]) -> Vec<bool> {
+        suffixes
+            .iter()
+            .map(|suffix| {
+                let mut string = prefix.to_vec();
+                string.extend_from_slice(suffix);
+                self.query(string)
+            })
+            .collect()
+    }
+
+    fn query(&mut self, string: Vec<A::Symbol>) -> bool {
+        if let Some(member) = self.cache.get(&string) {
+            return *member;
+        }
+
+        let member = self.membership.is_member(&string);
+        self.cache.insert(string, member);
+
+        member
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet::{Boolean, IntoAlphabet},
+        dfa::DefaultDFAStorage,
+        learning::DFAEquivalenceOracle,
+        test_helper::CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA,
+        util::Range,
+    };
+    #[cfg(feature = "rand")]
+    use crate::{learning::RandomSamplingOracle, test_helper::CONTAINS_TWO_FALSE_DFA};
+    #[cfg(feature = "rand")]
+    use rand_core::SeedableRng;
+    #[cfg(feature = "rand")]
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn learn_from_dfa_oracle() {
+        let target = &*CONTAINS_EVEN_TRUES_OR_TWO_FALSE_DFA;
+        let mut learner = LStar::new(
+            Boolean,
+            |string: &[bool]| target.accept(string.iter().copied()),
+            DFAEquivalenceOracle::new(target),
+        );
+
+        let learned: DFA<Boolean> = learner.learn().unwrap();
+
+        assert!(learned.is_equivalent(target).unwrap());
+        assert_eq!(learned.storage().all_states().len(), 5);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn learn_from_random_sampling() {
+        let target = &*CONTAINS_TWO_FALSE_DFA;
+        let membership = |string: &[bool]| target.accept(string.iter().copied());
+        let mut learner = LStar::new(
+            Boolean,
+            membership,
+            RandomSamplingOracle::new(membership, XorShiftRng::seed_from_u64(7), 200, 10),
+        );
+
+        let learned: DFA<Boolean> = learner.learn().unwrap();
+
+        assert!(learned.is_equivalent(target).unwrap());
+        assert!(learner.num_membership_queries() > 0);
+    }
+
+    #[test]
+    fn oracle_errors() {
+        let target: DFA<Range<char>> = {
+            let mut builder = DFABuilder::new('a'..='b');
+            let q0 = builder.new_state();
+            builder
+                .transitions(vec![(q0, 'a', q0), (q0, 'b', q0)])
+                .unwrap();
+            builder.start_state(q0);
+            builder.build().unwrap()
+        };
+        let mut learner = LStar::new(
+            ('a'..='c').into_alphabet(),
+            |_: &[char]| false,
+            DFAEquivalenceOracle::new(&target),
+        );
+        assert!(matches!(
+            learner.learn::<DefaultDFAStorage<_, _>>(),
+            Err(Error::OperationWithNonEqualAlphabets { .. })
+        ));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn inconsistent_oracles() {
+        // The equivalence oracle disagrees with the membership oracle on the
+        // empty string, which is always a suffix already.
+        let mut learner = LStar::new(
+            Boolean,
+            |string: &[bool]| string.is_empty(),
+            RandomSamplingOracle::new(|_: &[bool]| false, XorShiftRng::seed_from_u64(7), 10, 3),
+        );
+        assert!(matches!(
+            learner.learn::<DefaultDFAStorage<_, _>>(),
+            Err(Error::InconsistentExamples)
+        ));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_sampling_checks_empty_string() {
+        let mut builder = DFABuilder::new(Range::<char>::Empty);
+        let q0 = builder.new_state();
+        builder.start_state(q0);
+        let rejects_all: DFA<Range<char>> = builder.build().unwrap();
+
+        let mut oracle =
+            RandomSamplingOracle::new(|_: &[char]| true, XorShiftRng::seed_from_u64(7), 10, 3);
+        assert_eq!(oracle.counterexample(&rejects_all).unwrap(), Some(vec![]));
+
+        let mut oracle =
+            RandomSamplingOracle::new(|_: &[char]| false, XorShiftRng::seed_from_u64(7), 10, 3);
+        assert_eq!(oracle.counterexample(&rejects_all).unwrap(), None);
+    }
+}
@@ -0,0 +1,462 @@
+use crate::{
+    alphabet::Alphabet,
+    collections::{HashMap, VecDeque},
+    dfa::{DFABuilder, DFAStorage, DFA},
+    error::Error,
+    util::VecSet,
+};
+use alloc::{vec, vec::Vec};
+use core::{fmt::Debug, hash::Hash};
+
+/// How the passive learner chooses which states to merge.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MergeStrategy {
+    /// Merge each blue state, in breadth first order, into the first red state
+    /// it is compatible with (RPNI).
+    FirstCompatible,
+    /// Perform the compatible merge that unifies the most labelled states,
+    /// across all pairs of red and blue states (EDSM).
+    EvidenceDriven,
+}
+
+/// Passive learning of a DFA from positive and negative example strings.
+///
+/// The learner builds a prefix tree acceptor of the examples and then merges
+/// its states using the red-blue framework, never performing a merge that
+/// would accept a negative example or reject a positive one. The resulting
+/// DFA is consistent with every example.
+#[derive(Debug, Clone)]
+pub struct Rpni<A: Alphabet> {
+    alphabet: A,
+    positive: Vec<Vec<A::Symbol>>,
+    negative: Vec<Vec<A::Symbol>>,
+    strategy: MergeStrategy,
+}
+
+impl<A> Rpni<A>
+where
+    A: Alphabet + Clone,
+    A::Symbol: Clone + Eq + Hash,
+{
+    /// Create a new learner for a language over the given alphabet.
+    pub fn new(alphabet: A) -> Self {
+        Rpni {
+            alphabet,
+            positive: Vec::new(),
+            negative: Vec::new(),
+            strategy: MergeStrategy::FirstCompatible,
+        }
+    }
+
+    /// Add strings that must be accepted by the learned DFA.
+    pub fn positive<I, S>(&mut self, strings: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: IntoIterator<Item = A::Symbol>,
+    {
+        self.positive
+            .extend(strings.into_iter().map(|s| s.into_iter().collect()));
+
+        self
+    }
+
+    /// Add strings that must be rejected by the learned DFA.
+    pub fn negative<I, S>(&mut self, strings: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: IntoIterator<Item = A::Symbol>,
+    {
+        self.negative
+            .extend(strings.into_iter().map(|s| s.into_iter().collect()));
+
+        self
+    }
+
+    /// Set the strategy used to choose merges.
+    pub fn strategy(&mut self, strategy: MergeStrategy) -> &mut Self {
+        self.strategy = strategy;
+
+        self
+    }
+
+    /// Infer a DFA consistent with the examples.
+    ///
+    /// Transitions that no example exercises lead to a rejecting dead state.
+    ///
+    /// # Error
+    ///
+    /// This function will error if an example contains a symbol that is not in
+    /// the alphabet, or if a string is both a positive and a negative example.
+    pub fn learn<S>(&self) -> Result<DFA<A, S>, Error>
+    where
+        S: DFAStorage<A>,
+        S::State: Ord,
+        A::Symbol: Debug,
+    {
+        let symbols: Vec<A::Symbol> = self.alphabet.values().collect();
+        let mut automaton = self.prefix_tree()?;
+        let order = automaton.breadth_first_order(&symbols);
+
+        let mut red: VecSet<usize> = VecSet::new();
+        red.insert(0);
+
+        loop {
+            let blue = automaton.blue_states(&red, &symbols, &order);
+            if blue.is_empty() {
+                break;
+            }
+
+            match self.strategy {
+                MergeStrategy::FirstCompatible => {
+                    let (parent, sym, candidate) = blue[0].clone();
+                    let merged = red.iter().any(|&target| {
+                        automaton
+                            .try_merge(parent, &sym, target, candidate, &symbols, true)
+                            .is_some()
+                    });
+
+                    if !merged {
+                        red.insert(candidate);
+                    }
+                }
+                MergeStrategy::EvidenceDriven => {
+                    // The best merge so far, as the index of the blue
+                    // transition, the red state and the score.
+                    let mut best: Option<(usize, usize, usize)> = None;
+                    let mut promoted = None;
+
+                    for (idx, (parent, sym, candidate)) in blue.iter().enumerate() {
+                        let mut any_compatible = false;
+                        for &target in &red {
+                            if let Some(score) = automaton
+                                .try_merge(*parent, sym, target, *candidate, &symbols, false)
+                            {
+                                any_compatible = true;
+                                let better = match best {
+                                    Some((_, _, best_score)) => score > best_score,
+                                    None => true,
+                                };
+                                if better {
+                                    best = Some((idx, target, score));
+                                }
+                            }
+                        }
+
+                        if !any_compatible {
+                            promoted = Some(*candidate);
+                            break;
+                        }
+                    }
+
+                    // A blue state that cannot be merged anywhere must be promoted
+                    // before any merge is performed.
+                    if let Some(candidate) = promoted {
+                        red.insert(candidate);
+                    } else if let Some((idx, target, _)) = best {
+                        let (parent, sym, candidate) = &blue[idx];
+                        automaton.try_merge(*parent, sym, target, *candidate, &symbols, true);
+                    }
+                }
+            }
+        }
+
+        automaton.into_dfa(self.alphabet.clone(), &red, &symbols)
+    }
+
+    fn prefix_tree(&self) -> Result<PrefixTree<A::Symbol>, Error>
+    where
+        A::Symbol: Debug,
+    {
+        let mut tree = PrefixTree {
+            label: vec![None],
+            transitions: HashMap::new(),
+        };
+
+        let examples = self
+            .positive
+            .iter()
+            .map(|s| (s, true))
+            .chain(self.negative.iter().map(|s| (s, false)));
+        for (string, accepted) in examples {
+            let mut current = 0;
+            for sym in string {
+                if !self.alphabet.contains(sym) {
+                    return Err(Error::symbol_not_in_alphabet(sym));
+                }
+
+                current = match tree.transitions.get(&(current, sym.clone())) {
+                    Some(next) => *next,
+                    None => {
+                        tree.label.push(None);
+                        let next = tree.label.len() - 1;
+                        tree.transitions.insert((current, sym.clone()), next);
+                        next
+                    }
+                };
+            }
+
+            match tree.label[current] {
+                Some(label) if label != accepted => return Err(Error::InconsistentExamples),
+                _ => tree.label[current] = Some(accepted),
+            }
+        }
+
+        Ok(tree)
+    }
+}
+
+// A prefix tree acceptor that is progressively folded into a DFA. States are
+// never removed, a merged state just becomes unreachable.
+#[derive(Debug, Clone)]
+struct PrefixTree<Sym> {
+    label: Vec<Option<bool>>,
+    transitions: HashMap<(usize, Sym), usize>,
+}
+
+// A change made by a merge, recording the previous value so that merges can be
+// tried in place and undone, instead of on a copy of the whole tree.
+#[derive(Debug)]
+enum Change<Sym> {
+    Label(usize, Option<bool>),
+    Transition((usize, Sym), Option<usize>),
+}
+
+impl<Sym> PrefixTree<Sym>
+where
+    Sym: Clone + Eq + Hash,
+{
+    // The rank of every state in a breadth first traversal, which is the order
+    // that blue states are considered in.
+    fn breadth_first_order(&self, symbols: &[Sym]) -> Vec<usize> {
+        let mut order = vec![usize::MAX; self.label.len()];
+        let mut queue = VecDeque::new();
+        let mut rank = 0;
+
+        queue.push_back(0);
+        while let Some(state) = queue.pop_front() {
+            order[state] = rank;
+            rank += 1;
+
+            for sym in symbols {
+                if let Some(next) = self.transitions.get(&(state, sym.clone())) {
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        order
+    }
+
+    // The states one transition away from a red state that are not red
+    // themselves, along with the transition that reaches them.
+    fn blue_states(
+        &self,
+        red: &VecSet<usize>,
+        symbols: &[Sym],
+        order: &[usize],
+    ) -> Vec<(usize, Sym, usize)> {
+        let mut blue = Vec::new();
+        for &state in red {
+            for sym in symbols {
+                if let Some(next) = self.transitions.get(&(state, sym.clone())) {
+                    if !red.contains(next) {
+                        blue.push((state, sym.clone(), *next));
+                    }
+                }
+            }
+        }
+
+        blue.sort_by_key(|(_, _, state)| order[*state]);
+        blue
+    }
+
+    // Redirect the transition into `blue` to `red`, and fold the subtree rooted
+    // at `blue` into `red`. Return the number of labelled states that were
+    // unified, or `None` if the merge would join an accepting and a rejecting
+    // state.
+    //
+    // The merge is kept only if it succeeds and `keep` is set, otherwise the
+    // tree is left unchanged.
+    fn try_merge(
+        &mut self,
+        parent: usize,
+        sym: &Sym,
+        red: usize,
+        blue: usize,
+        symbols: &[Sym],
+        keep: bool,
+    ) -> Option<usize> {
+        let mut log = Vec::new();
+        self.set_transition((parent, sym.clone()), red, &mut log);
+
+        let score = self.fold(red, blue, symbols, &mut log);
+        if score.is_none() || !keep {
+            self.undo(log);
+        }
+
+        score
+    }
+
+    fn fold(
+        &mut self,
+        red: usize,
+        blue: usize,
+        symbols: &[Sym],
+        log: &mut Vec<Change<Sym>>,
+    ) -> Option<usize> {
+        let mut score = match (self.label[red], self.label[blue]) {
+            (Some(red_label), Some(blue_label)) if red_label != blue_label => return None,
+            (Some(_), Some(_)) => 1,
+            (None, blue_label) => {
+                log.push(Change::Label(red, None));
+                self.label[red] = blue_label;
+                0
+            }
+            (Some(_), None) => 0,
+        };
+
+        for sym in symbols {
+            if let Some(blue_next) = self.transitions.get(&(blue, sym.clone())).copied() {
+                match self.transitions.get(&(red, sym.clone())).copied() {
+                    Some(red_next) => score += self.fold(red_next, blue_next, symbols, log)?,
+                    None => self.set_transition((red, sym.clone()), blue_next, log),
+                }
+            }
+        }
+
+        Some(score)
+    }
+
+    fn set_transition(&mut self, key: (usize, Sym), next: usize, log: &mut Vec<Change<Sym>>) {
+        let previous = self.transitions.insert(key.clone(), next);
+        log.push(Change::Transition(key, previous));
+    }
+
+    // Revert the changes in the log, most recent first.
+    fn undo(&mut self, log: Vec<Change<Sym>>) {
+        for change in log.into_iter().rev() {
+            match change {
+                Change::Label(state, label) => self.label[state] = label,
+                Change::Transition(key, Some(previous)) => {
+                    self.transitions.insert(key, previous);
+                }
+                Change::Transition(key, None) => {
+                    self.transitions.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn into_dfa<A, S>(
+        self,
+        alphabet: A,
+        red: &VecSet<usize>,
+        symbols: &[Sym],
+    ) -> Result<DFA<A, S>, Error>
+    where
+        A: Alphabet<Symbol = Sym>,
+        S: DFAStorage<A>,
+        S::State: Ord,
+    {
+        let mut builder = DFABuilder::new_with_storage(S::from_alphabet(alphabet));
+        let states: HashMap<usize, S::State> = red
+            .iter()
+            .map(|state| (*state, builder.new_state()))
+            .collect();
+        let mut dead = None;
+
+        for state in red {
+            let new_state = &states[state];
+            for sym in symbols {
+                let new_next = match self.transitions.get(&(*state, sym.clone())) {
+                    Some(next) => states.get(next).cloned().ok_or(Error::StateNotFound)?,
+                    None => dead.get_or_insert_with(|| builder.new_state()).clone(),
+                };
+
+                builder.add_transition(new_state.clone(), sym.clone(), new_next);
+            }
+        }
+
+        if let Some(dead) = dead.clone() {
+            for sym in symbols {
+                builder.add_transition(dead.clone(), sym.clone(), dead.clone());
+            }
+        }
+
+        let accept = red
+            .iter()
+            .filter(|state| self.label[**state] == Some(true))
+            .map(|state| states[state].clone());
+
+        builder
+            .start_state(states[&0].clone())
+            .accept_states(accept)
+            .dead_state(dead);
+
+        Ok(builder.build_unchecked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet::Boolean,
+        test_helper::{binary_converter, convert_string},
+    };
+
+    fn strings(raw: &[&str]) -> Vec<Vec<bool>> {
+        raw.iter()
+            .map(|s| convert_string(s.to_string(), binary_converter()))
+            .collect()
+    }
+
+    fn even_trues_examples(strategy: MergeStrategy) -> DFA<Boolean> {
+        Rpni::new(Boolean)
+            .positive(strings(&["", "0", "11", "00", "101", "110", "011", "1111"]))
+            .negative(strings(&["1", "01", "10", "111", "001", "100", "1011"]))
+            .strategy(strategy)
+            .learn()
+            .unwrap()
+    }
+
+    #[test]
+    fn consistent_with_examples() {
+        for strategy in &[
+            MergeStrategy::FirstCompatible,
+            MergeStrategy::EvidenceDriven,
+        ] {
+            let dfa = even_trues_examples(*strategy);
+
+            for positive in strings(&["", "0", "11", "00", "101", "110", "011", "1111"]) {
+                assert!(dfa.accept(positive));
+            }
+            for negative in strings(&["1", "01", "10", "111", "001", "100", "1011"]) {
+                assert!(!dfa.accept(negative));
+            }
+        }
+    }
+
+    #[test]
+    fn generalizes_to_even_trues() {
+        let dfa = even_trues_examples(MergeStrategy::FirstCompatible);
+
+        assert_eq!(dfa.storage().all_states().len(), 2);
+        for string in strings(&["0110", "111111", "0101"]) {
+            assert!(dfa.accept(string));
+        }
+        for string in strings(&["0111", "11111", "0100"]) {
+            assert!(!dfa.accept(string));
+        }
+    }
+
+    #[test]
+    fn conflicting_examples() {
+        let result: Result<DFA<Boolean>, Error> = Rpni::new(Boolean)
+            .positive(strings(&["01"]))
+            .negative(strings(&["01"]))
+            .learn();
+
+        assert!(matches!(result, Err(Error::InconsistentExamples)));
+    }
+}
@@ -1,7 +1,19 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs, clippy::missing_safety_doc)]
 
 //! Tools for manipulating regular languages.
 //!
+//! The crate supports `no_std` environments with `alloc` when the default
+//! `std` feature is disabled. Without `std`, the `hashbrown` feature must be
+//! enabled to provide hash maps, and `HashSet` is no longer an `Alphabet`.
+//!
+//! The `rand` feature adds `DFA::random` and the random sampling equivalence
+//! oracle `learning::RandomSamplingOracle`.
+//!
+//! The `rayon` feature adds parallel versions of the product constructions,
+//! such as `DFA::par_intersection`, which find the transitions of the product
+//! in parallel and give the same result as their sequential counterparts.
+//!
 //! # Papers of interest
 //!
 //! EFFICIENT MINIMIZATION OF DFAS WITH PARTIAL TRANSITION FUNCTIONS
@@ -34,6 +46,8 @@
 //!     an efficient implementation and description of data structures for
 //!     subset construction (determinization)
 
+extern crate alloc;
+
 /// Generalization of the accept/non-accept of regular expressions, DFAs, and
 /// NFAs. Connected to the larger concept of recognizing that some string
 /// belongs to a language.
@@ -41,17 +55,37 @@ pub mod accept;
 /// Traits and implementations of generic sets of symbol, called alphabets in
 /// the context of languages and strings.
 pub mod alphabet;
+#[cfg(any(test, feature = "proptest"))]
+pub(crate) mod arbitrary;
+pub(crate) mod collections;
 /// Implementation of discrete finite automaton.
 pub(crate) mod dfa;
 pub(crate) mod error;
+/// Algorithms for learning DFAs, either actively from oracles or passively
+/// from examples.
+pub mod learning;
+/// Linear temporal logic over finite traces, and its compilation to DFAs.
+pub(crate) mod ltlf;
+/// Traits and implementations of semirings, the weights used by weighted
+/// automata.
+pub mod semiring;
 pub(crate) mod util;
+/// Implementation of weighted finite automaton.
+pub(crate) mod weighted;
 
 #[cfg(test)]
 pub(crate) mod test_helper;
 
-pub use dfa::{DFABuilder, DFAStorage, DefaultDFAStorage, DFA};
+#[cfg(feature = "proptest")]
+pub use arbitrary::DFAParams;
+pub use dfa::{
+    BinaryAlphabet, BinaryDFAStorage, BuchiAutomaton, CodegenStyle, DFABuilder, DFAStorage,
+    DefaultDFAStorage, RustSymbol, SymbolTable, ValidationReport, DFA,
+};
 pub use error::Error;
+pub use ltlf::Ltlf;
 pub use util::Range;
+pub use weighted::WeightedAutomaton;
 
 /// Common items to import.
 ///
@@ -0,0 +1,448 @@
+use crate::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    dfa::{DFABuilder, DFA},
+    error::Error,
+    util::Range,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+
+// The most propositions in a compiled formula, so that every valuation fits in
+// a `u32` alphabet.
+const MAX_PROPS: u32 = 31;
+
+/// A formula of linear temporal logic over finite traces (LTLf).
+///
+/// A trace is a non-empty string of valuations, where each valuation is a
+/// bitmask of the propositions that hold at that position: proposition `p`
+/// holds when bit `p` is set.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Ltlf {
+    /// Holds at every position.
+    True,
+    /// Holds at no position.
+    False,
+    /// Holds when the proposition with the given index holds.
+    Atom(u32),
+    /// Holds when the inner formula does not.
+    Not(Box<Ltlf>),
+    /// Holds when both formulas hold.
+    And(Box<Ltlf>, Box<Ltlf>),
+    /// Holds when either formula holds.
+    Or(Box<Ltlf>, Box<Ltlf>),
+    /// Holds when there is a next position and the formula holds there.
+    Next(Box<Ltlf>),
+    /// Holds when there is no next position or the formula holds there.
+    WeakNext(Box<Ltlf>),
+    /// Holds when the second formula holds at some position, and the first
+    /// formula holds at every position before it.
+    Until(Box<Ltlf>, Box<Ltlf>),
+    /// Holds when the second formula holds up to and including the first
+    /// position where the first formula holds, or at every position if there
+    /// is no such position.
+    Release(Box<Ltlf>, Box<Ltlf>),
+    /// Holds when the formula holds at some position.
+    Eventually(Box<Ltlf>),
+    /// Holds when the formula holds at every position.
+    Always(Box<Ltlf>),
+}
+
+impl Ltlf {
+    /// Create an atomic formula for the proposition with the given index.
+    pub fn atom(prop: u32) -> Self {
+        Ltlf::Atom(prop)
+    }
+
+    /// Create the negation of a formula.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(inner: Ltlf) -> Self {
+        Ltlf::Not(Box::new(inner))
+    }
+
+    /// Create the conjunction of two formulas.
+    pub fn and(left: Ltlf, right: Ltlf) -> Self {
+        Ltlf::And(Box::new(left), Box::new(right))
+    }
+
+    /// Create the disjunction of two formulas.
+    pub fn or(left: Ltlf, right: Ltlf) -> Self {
+        Ltlf::Or(Box::new(left), Box::new(right))
+    }
+
+    /// Create the formula `left -> right`.
+    pub fn implies(left: Ltlf, right: Ltlf) -> Self {
+        Ltlf::or(Ltlf::not(left), right)
+    }
+
+    /// Create the strong next of a formula.
+    pub fn next(inner: Ltlf) -> Self {
+        Ltlf::Next(Box::new(inner))
+    }
+
+    /// Create the weak next of a formula.
+    pub fn weak_next(inner: Ltlf) -> Self {
+        Ltlf::WeakNext(Box::new(inner))
+    }
+
+    /// Create the formula `left U right`.
+    pub fn until(left: Ltlf, right: Ltlf) -> Self {
+        Ltlf::Until(Box::new(left), Box::new(right))
+    }
+
+    /// Create the formula `left R right`.
+    pub fn release(left: Ltlf, right: Ltlf) -> Self {
+        Ltlf::Release(Box::new(left), Box::new(right))
+    }
+
+    /// Create the formula `F inner`.
+    pub fn eventually(inner: Ltlf) -> Self {
+        Ltlf::Eventually(Box::new(inner))
+    }
+
+    /// Create the formula `G inner`.
+    pub fn always(inner: Ltlf) -> Self {
+        Ltlf::Always(Box::new(inner))
+    }
+
+    /// Compile this formula into a DFA that accepts exactly the traces that
+    /// satisfy it.
+    ///
+    /// The alphabet of the DFA is every valuation of `num_props` propositions,
+    /// the range `0..2^num_props`. Since traces are non-empty, the empty string
+    /// is always rejected.
+    ///
+    /// The construction is by formula progression: each state is a formula, in
+    /// disjunctive normal form, that the rest of the trace must satisfy.
+    ///
+    /// # Error
+    ///
+    /// This function will error with `Error::TooManyPropositions` if
+    /// `num_props` is greater than 31, and with `Error::InvalidProposition` if
+    /// the formula refers to a proposition with an index of at least
+    /// `num_props`.
+    pub fn compile(&self, num_props: u32) -> Result<DFA<Range<u32>>, Error> {
+        if num_props > MAX_PROPS {
+            return Err(Error::TooManyPropositions {
+                num_props,
+                max: MAX_PROPS,
+            });
+        }
+        if let Some(index) = self.max_prop().filter(|&prop| prop >= num_props) {
+            return Err(Error::InvalidProposition { index, num_props });
+        }
+
+        let mut builder = DFABuilder::new(0..(1_u32 << num_props));
+        let mut states: BTreeMap<Dnf, usize> = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        let mut accept = Vec::new();
+
+        let start = dnf_literal(Obligation::Strong(self.negation_normal_form()));
+        let start_state = builder.new_state();
+        states.insert(start.clone(), start_state);
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            let current_state = states[&current];
+
+            // A trace may end here if some clause contains only weak obligations.
+            if current
+                .iter()
+                .any(|clause| clause.iter().all(|ob| matches!(ob, Obligation::Weak(_))))
+            {
+                accept.push(current_state);
+            }
+
+            for valuation in 0..(1_u32 << num_props) {
+                let next = progress(&current, valuation);
+                let next_state = match states.get(&next) {
+                    Some(state) => *state,
+                    None => {
+                        let state = builder.new_state();
+                        states.insert(next.clone(), state);
+                        queue.push_back(next);
+                        state
+                    }
+                };
+
+                builder.transition(current_state, valuation, next_state)?;
+            }
+        }
+
+        builder
+            .start_state(start_state)
+            .accept_states(accept)
+            .dead_state(states.get(&Dnf::new()).copied());
+
+        builder.build()
+    }
+
+    fn max_prop(&self) -> Option<u32> {
+        match self {
+            Ltlf::True | Ltlf::False => None,
+            Ltlf::Atom(prop) => Some(*prop),
+            Ltlf::Not(inner)
+            | Ltlf::Next(inner)
+            | Ltlf::WeakNext(inner)
+            | Ltlf::Eventually(inner)
+            | Ltlf::Always(inner) => inner.max_prop(),
+            Ltlf::And(left, right)
+            | Ltlf::Or(left, right)
+            | Ltlf::Until(left, right)
+            | Ltlf::Release(left, right) => left.max_prop().max(right.max_prop()),
+        }
+    }
+
+    // Push all negations down to the atoms, using the LTLf dualities.
+    fn negation_normal_form(&self) -> Ltlf {
+        match self {
+            Ltlf::True | Ltlf::False | Ltlf::Atom(_) => self.clone(),
+            Ltlf::Not(inner) => inner.negated(),
+            Ltlf::And(left, right) => {
+                Ltlf::and(left.negation_normal_form(), right.negation_normal_form())
+            }
+            Ltlf::Or(left, right) => {
+                Ltlf::or(left.negation_normal_form(), right.negation_normal_form())
+            }
+            Ltlf::Next(inner) => Ltlf::next(inner.negation_normal_form()),
+            Ltlf::WeakNext(inner) => Ltlf::weak_next(inner.negation_normal_form()),
+            Ltlf::Until(left, right) => {
+                Ltlf::until(left.negation_normal_form(), right.negation_normal_form())
+            }
+            Ltlf::Release(left, right) => {
+                Ltlf::release(left.negation_normal_form(), right.negation_normal_form())
+            }
+            Ltlf::Eventually(inner) => Ltlf::eventually(inner.negation_normal_form()),
+            Ltlf::Always(inner) => Ltlf::always(inner.negation_normal_form()),
+        }
+    }
+
+    // The negation normal form of the negation of this formula.
+    fn negated(&self) -> Ltlf {
+        match self {
+            Ltlf::True => Ltlf::False,
+            Ltlf::False => Ltlf::True,
+            Ltlf::Atom(_) => Ltlf::not(self.clone()),
+            Ltlf::Not(inner) => inner.negation_normal_form(),
+            Ltlf::And(left, right) => Ltlf::or(left.negated(), right.negated()),
+            Ltlf::Or(left, right) => Ltlf::and(left.negated(), right.negated()),
+            Ltlf::Next(inner) => Ltlf::weak_next(inner.negated()),
+            Ltlf::WeakNext(inner) => Ltlf::next(inner.negated()),
+            Ltlf::Until(left, right) => Ltlf::release(left.negated(), right.negated()),
+            Ltlf::Release(left, right) => Ltlf::until(left.negated(), right.negated()),
+            Ltlf::Eventually(inner) => Ltlf::always(inner.negated()),
+            Ltlf::Always(inner) => Ltlf::eventually(inner.negated()),
+        }
+    }
+}
+
+// A requirement on the rest of the trace. A strong obligation requires the
+// rest of the trace to be non-empty and satisfy the formula, while a weak
+// obligation is also met by an empty rest of the trace.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Obligation {
+    Strong(Ltlf),
+    Weak(Ltlf),
+}
+
+type Clause = BTreeSet<Obligation>;
+
+// A disjunction of conjunctions of obligations. The empty disjunction is
+// unsatisfiable, and a disjunction containing the empty conjunction is always
+// satisfied.
+type Dnf = BTreeSet<Clause>;
+
+fn dnf_true() -> Dnf {
+    vec![Clause::new()].into_iter().collect()
+}
+
+fn dnf_literal(obligation: Obligation) -> Dnf {
+    vec![vec![obligation].into_iter().collect()]
+        .into_iter()
+        .collect()
+}
+
+fn dnf_or(left: Dnf, right: Dnf) -> Dnf {
+    absorb(left.into_iter().chain(right).collect())
+}
+
+fn dnf_and(left: &Dnf, right: &Dnf) -> Dnf {
+    let mut product = Dnf::new();
+    for left_clause in left {
+        for right_clause in right {
+            product.insert(left_clause.union(right_clause).cloned().collect());
+        }
+    }
+
+    absorb(product)
+}
+
+// Remove every clause that is implied by a smaller clause, which keeps the
+// number of distinct states small.
+fn absorb(dnf: Dnf) -> Dnf {
+    dnf.iter()
+        .filter(|clause| {
+            !dnf.iter()
+                .any(|other| other != *clause && other.is_subset(clause))
+        })
+        .cloned()
+        .collect()
+}
+
+// Consume one valuation from the front of the trace.
+fn progress(current: &Dnf, valuation: u32) -> Dnf {
+    let mut next = Dnf::new();
+    for clause in current {
+        let mut progressed = dnf_true();
+        for obligation in clause {
+            let formula = match obligation {
+                Obligation::Strong(formula) | Obligation::Weak(formula) => formula,
+            };
+            progressed = dnf_and(&progressed, &unfold(formula, valuation));
+        }
+
+        next = dnf_or(next, progressed);
+    }
+
+    next
+}
+
+// The obligations on the rest of the trace for `formula` to hold at a position
+// with the given valuation. The formula must be in negation normal form.
+fn unfold(formula: &Ltlf, valuation: u32) -> Dnf {
+    let holds = |prop: &u32| valuation & (1 << prop) != 0;
+
+    match formula {
+        Ltlf::True => dnf_true(),
+        Ltlf::False => Dnf::new(),
+        Ltlf::Atom(prop) if holds(prop) => dnf_true(),
+        Ltlf::Atom(_) => Dnf::new(),
+        Ltlf::Not(inner) => match inner.as_ref() {
+            Ltlf::Atom(prop) if holds(prop) => Dnf::new(),
+            Ltlf::Atom(_) => dnf_true(),
+            _ => unreachable!("formula is in negation normal form"),
+        },
+        Ltlf::And(left, right) => dnf_and(&unfold(left, valuation), &unfold(right, valuation)),
+        Ltlf::Or(left, right) => dnf_or(unfold(left, valuation), unfold(right, valuation)),
+        Ltlf::Next(inner) => dnf_literal(Obligation::Strong(inner.as_ref().clone())),
+        Ltlf::WeakNext(inner) => dnf_literal(Obligation::Weak(inner.as_ref().clone())),
+        Ltlf::Until(left, right) => dnf_or(
+            unfold(right, valuation),
+            dnf_and(
+                &unfold(left, valuation),
+                &dnf_literal(Obligation::Strong(formula.clone())),
+            ),
+        ),
+        Ltlf::Release(left, right) => dnf_and(
+            &unfold(right, valuation),
+            &dnf_or(
+                unfold(left, valuation),
+                dnf_literal(Obligation::Weak(formula.clone())),
+            ),
+        ),
+        Ltlf::Eventually(inner) => dnf_or(
+            unfold(inner, valuation),
+            dnf_literal(Obligation::Strong(formula.clone())),
+        ),
+        Ltlf::Always(inner) => dnf_and(
+            &unfold(inner, valuation),
+            &dnf_literal(Obligation::Weak(formula.clone())),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u32 = 0b01;
+    const Q: u32 = 0b10;
+
+    #[test]
+    fn eventually_and_always() {
+        let eventually_p = Ltlf::eventually(Ltlf::atom(0)).compile(2).unwrap();
+
+        assert!(eventually_p.accept(vec![P]));
+        assert!(eventually_p.accept(vec![0, Q, P | Q, 0]));
+        assert!(!eventually_p.accept(vec![0, Q]));
+        assert!(!eventually_p.accept(vec![]));
+
+        let always_p = Ltlf::always(Ltlf::atom(0)).compile(2).unwrap();
+
+        assert!(always_p.accept(vec![P, P | Q, P]));
+        assert!(!always_p.accept(vec![P, Q, P]));
+    }
+
+    #[test]
+    fn until_and_release() {
+        let p_until_q = Ltlf::until(Ltlf::atom(0), Ltlf::atom(1))
+            .compile(2)
+            .unwrap();
+
+        assert!(p_until_q.accept(vec![P, P, Q]));
+        assert!(p_until_q.accept(vec![Q]));
+        assert!(!p_until_q.accept(vec![P, P]));
+        assert!(!p_until_q.accept(vec![P, 0, Q]));
+
+        let p_release_q = Ltlf::release(Ltlf::atom(0), Ltlf::atom(1))
+            .compile(2)
+            .unwrap();
+
+        assert!(p_release_q.accept(vec![Q, Q]));
+        assert!(p_release_q.accept(vec![Q, P | Q, 0]));
+        assert!(!p_release_q.accept(vec![Q, P, 0]));
+    }
+
+    #[test]
+    fn strong_and_weak_next() {
+        let next_p = Ltlf::next(Ltlf::atom(0)).compile(1).unwrap();
+        let weak_next_p = Ltlf::weak_next(Ltlf::atom(0)).compile(1).unwrap();
+        let not_next_p = Ltlf::not(Ltlf::next(Ltlf::atom(0))).compile(1).unwrap();
+
+        assert!(next_p.accept(vec![0, P]));
+        assert!(!next_p.accept(vec![P]));
+        assert!(weak_next_p.accept(vec![P]));
+        assert!(!weak_next_p.accept(vec![P, 0]));
+        assert!(not_next_p.accept(vec![P]));
+        assert!(not_next_p.accept(vec![P, 0]));
+        assert!(!not_next_p.accept(vec![0, P]));
+    }
+
+    #[test]
+    fn response_monitor() {
+        // G(request -> F ack)
+        let response = Ltlf::always(Ltlf::implies(
+            Ltlf::atom(0),
+            Ltlf::eventually(Ltlf::atom(1)),
+        ))
+        .compile(2)
+        .unwrap();
+
+        assert!(response.accept(vec![0, P, 0, Q]));
+        assert!(response.accept(vec![P | Q]));
+        assert!(!response.accept(vec![P, Q, P]));
+    }
+
+    #[test]
+    fn proposition_errors() {
+        assert!(matches!(
+            Ltlf::and(Ltlf::atom(0), Ltlf::atom(5)).compile(2),
+            Err(Error::InvalidProposition {
+                index: 5,
+                num_props: 2
+            })
+        ));
+        assert!(matches!(
+            Ltlf::atom(0).compile(40),
+            Err(Error::TooManyPropositions {
+                num_props: 40,
+                max: 31
+            })
+        ));
+        assert!(matches!(
+            Ltlf::atom(2).compile(2),
+            Err(Error::InvalidProposition {
+                index: 2,
+                num_props: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+use core::ops;
+
+/// A set of weights with two operations, `plus` and `times`, that distribute
+/// over each other.
+///
+/// `plus` combines the weights of alternative paths and must be associative
+/// and commutative with identity `zero`. `times` combines the weights along a
+/// single path and must be associative with identity `one`. `zero` must
+/// annihilate under `times`.
+pub trait Semiring: Clone + PartialEq {
+    /// The identity of `plus` and the annihilator of `times`.
+    fn zero() -> Self;
+    /// The identity of `times`.
+    fn one() -> Self;
+    /// Combine the weights of two alternative paths.
+    fn plus(&self, other: &Self) -> Self;
+    /// Combine the weights of two consecutive path segments.
+    fn times(&self, other: &Self) -> Self;
+
+    /// Return `true` if this weight is the `zero` of the semiring.
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+/// Marker for semirings where `a.plus(&a) == a` for every weight `a`.
+///
+/// Shortest-distance computations over an idempotent semiring only ever
+/// improve a distance, which is what guarantees their termination.
+pub trait Idempotent: Semiring {}
+
+/// The boolean semiring `({false, true}, or, and, false, true)`.
+///
+/// A weighted automaton over this semiring recognizes exactly the same
+/// language as an unweighted automaton.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Boolean(pub bool);
+
+impl Semiring for Boolean {
+    fn zero() -> Self {
+        Boolean(false)
+    }
+
+    fn one() -> Self {
+        Boolean(true)
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        Boolean(self.0 || other.0)
+    }
+
+    fn times(&self, other: &Self) -> Self {
+        Boolean(self.0 && other.0)
+    }
+}
+
+impl Idempotent for Boolean {}
+
+/// The tropical semiring `(R ∪ {+∞}, min, +, +∞, 0)`.
+///
+/// Weights are costs: the weight of a string is the cost of its cheapest
+/// accepting path.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Tropical(pub f64);
+
+impl Semiring for Tropical {
+    fn zero() -> Self {
+        Tropical(f64::INFINITY)
+    }
+
+    fn one() -> Self {
+        Tropical(0.0)
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        Tropical(self.0.min(other.0))
+    }
+
+    fn times(&self, other: &Self) -> Self {
+        Tropical(self.0 + other.0)
+    }
+}
+
+impl Idempotent for Tropical {}
+
+/// The probability semiring `(R+, +, *, 0, 1)`.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Probability(pub f64);
+
+impl Semiring for Probability {
+    fn zero() -> Self {
+        Probability(0.0)
+    }
+
+    fn one() -> Self {
+        Probability(1.0)
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        Probability(self.0 + other.0)
+    }
+
+    fn times(&self, other: &Self) -> Self {
+        Probability(self.0 * other.0)
+    }
+}
+
+/// The counting semiring `(N, +, *, 0, 1)`.
+///
+/// The weight of a string is the number of distinct accepting paths for it.
+/// Arithmetic saturates at `u64::MAX` instead of overflowing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Counting(pub u64);
+
+impl Semiring for Counting {
+    fn zero() -> Self {
+        Counting(0)
+    }
+
+    fn one() -> Self {
+        Counting(1)
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        Counting(self.0.saturating_add(other.0))
+    }
+
+    fn times(&self, other: &Self) -> Self {
+        Counting(self.0.saturating_mul(other.0))
+    }
+}
+
+macro_rules! impl_semiring_ops {
+    ($($t:ident),*) => {
+        $(
+            impl ops::Add for $t {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    Semiring::plus(&self, &rhs)
+                }
+            }
+
+            impl ops::Mul for $t {
+                type Output = Self;
+
+                fn mul(self, rhs: Self) -> Self {
+                    Semiring::times(&self, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_semiring_ops!(Boolean, Tropical, Probability, Counting);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tropical_identities() {
+        let w = Tropical(2.5);
+
+        assert_eq!(w + Tropical::zero(), w);
+        assert_eq!(w * Tropical::one(), w);
+        assert_eq!(w * Tropical::zero(), Tropical::zero());
+        assert_eq!(Tropical(1.0) + Tropical(3.0), Tropical(1.0));
+        assert_eq!(Tropical(1.0) * Tropical(3.0), Tropical(4.0));
+    }
+
+    #[test]
+    fn counting_saturates() {
+        assert_eq!(Counting(u64::MAX) + Counting(1), Counting(u64::MAX));
+        assert_eq!(Counting(u64::MAX) * Counting(2), Counting(u64::MAX));
+        assert_eq!(Counting(3) * Counting::zero(), Counting::zero());
+    }
+
+    #[test]
+    fn boolean_matches_logic() {
+        assert_eq!(Boolean(true) + Boolean(false), Boolean(true));
+        assert_eq!(Boolean(true) * Boolean(false), Boolean(false));
+        assert!(Boolean::zero().is_zero());
+    }
+}
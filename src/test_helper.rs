@@ -1,10 +1,10 @@
 use crate::{
     alphabet::Boolean,
+    collections::HashMap,
     dfa::{DFABuilder, DFA},
 };
 use core::iter::once;
 use once_cell::sync::Lazy;
-use std::collections::HashMap;
 
 pub fn convert_string<T: Clone>(raw: String, convert: HashMap<char, T>) -> Vec<T> {
     raw.chars()
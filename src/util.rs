@@ -1,14 +1,16 @@
 mod bit_set;
-mod cartesian_product;
 mod join_iter;
+#[cfg(feature = "rand")]
+mod random;
 mod set_partition;
 mod step;
 mod storage_int;
 mod vec_set;
 
 pub use bit_set::FixedBitSet;
-pub use cartesian_product::CartesianProductIter;
 pub use join_iter::Join;
+#[cfg(feature = "rand")]
+pub use random::uniform_below;
 pub use set_partition::SetPartitions;
 pub use step::{Bounded, Range, Step};
 pub use storage_int::StorageInt;
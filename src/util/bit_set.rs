@@ -1,4 +1,5 @@
 use crate::util::StorageInt;
+use alloc::{boxed::Box, vec};
 use core::{fmt, mem::size_of};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -0,0 +1,20 @@
+use rand_core::Rng;
+
+/// Return a number chosen uniformly from `0..bound`.
+///
+/// # Panics
+/// This function will panic if `bound` is zero.
+pub fn uniform_below<R: Rng>(rng: &mut R, bound: usize) -> usize {
+    assert!(bound > 0, "cannot choose from an empty range");
+    let bound = bound as u64;
+
+    // Reject the lowest `2^64 mod bound` values, so that every remainder is
+    // equally likely.
+    let threshold = bound.wrapping_neg() % bound;
+    loop {
+        let value = rng.next_u64();
+        if value >= threshold {
+            return (value % bound) as usize;
+        }
+    }
+}
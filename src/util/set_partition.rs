@@ -1,4 +1,5 @@
 use crate::util::FixedBitSet;
+use alloc::{boxed::Box, vec, vec::Vec};
 use core::{
     cmp::Ordering,
     ops::{Bound, RangeBounds},
@@ -7,6 +7,7 @@ use core::{
 
 /// Set of contiguous elements
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Range<Sym: Step> {
     /// Non-empty range
     NonEmpty {
@@ -32,6 +33,31 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de, Sym> serde::Deserialize<'de> for Range<Sym>
+where
+    Sym: Step + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Same representation as `Range`, without the check that the start is
+        // not after the end.
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Range")]
+        enum Unchecked<Sym> {
+            NonEmpty { start: Sym, end: Sym },
+            Empty,
+        }
+
+        match Unchecked::deserialize(deserializer)? {
+            Unchecked::NonEmpty { start, end } if start > end => {
+                Err(serde::de::Error::custom("range start is after its end"))
+            }
+            Unchecked::NonEmpty { start, end } => Ok(Range::NonEmpty { start, end }),
+            Unchecked::Empty => Ok(Range::Empty),
+        }
+    }
+}
+
 impl<Sym> Iterator for Range<Sym>
 where
     Sym: Step,
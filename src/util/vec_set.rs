@@ -1,10 +1,10 @@
 use crate::util::join_iter::Join;
+use alloc::vec::{IntoIter, Vec};
 use core::{
     cmp::Ordering,
     iter::{FromIterator, Peekable},
     slice::{Iter, IterMut},
 };
-use std::vec::IntoIter;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct VecSet<A> {
@@ -243,6 +243,28 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+impl<A> serde::Serialize for VecSet<A>
+where
+    A: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, A> serde::Deserialize<'de> for VecSet<A>
+where
+    A: Ord + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The elements may come from an untrusted source, so they are sorted
+        // and deduplicated rather than assumed to be a valid set.
+        Vec::<A>::deserialize(deserializer).map(|inner| inner.into_iter().collect())
+    }
+}
+
 fn cmp_opt<T: Ord>(x: Option<&T>, y: Option<&T>, short: Ordering, long: Ordering) -> Ordering {
     match (x, y) {
         (None, _) => short,
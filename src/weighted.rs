@@ -0,0 +1,333 @@
+use crate::{
+    alphabet::{Alphabet, IntoAlphabet},
+    collections::{HashMap, VecDeque},
+    dfa::{DFAStorage, DFA},
+    error::Error,
+    semiring::{Idempotent, Semiring},
+};
+use alloc::{vec, vec::Vec};
+use core::{fmt::Debug, hash::Hash};
+
+/// A weighted finite automaton over the semiring `K`.
+///
+/// Unlike a `DFA`, a weighted automaton may have several transitions leaving a
+/// state on the same symbol. The weight of a string is the `plus` over all
+/// accepting paths of the `times` of the transition weights along the path
+/// and the final weight of the last state.
+#[derive(Debug, Clone)]
+pub struct WeightedAutomaton<A: Alphabet, K> {
+    alphabet: A,
+    start: Option<usize>,
+    final_weight: Vec<K>,
+    transitions: Vec<Vec<(A::Symbol, K, usize)>>,
+}
+
+impl<A, K> WeightedAutomaton<A, K>
+where
+    A: Alphabet,
+    A::Symbol: PartialEq,
+    K: Semiring,
+{
+    /// Create a new, empty weighted automaton with the given alphabet.
+    pub fn new<I: IntoAlphabet<IntoAlpha = A, Symbol = A::Symbol>>(alphabet: I) -> Self {
+        WeightedAutomaton {
+            alphabet: alphabet.into_alphabet(),
+            start: None,
+            final_weight: Vec::new(),
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Create a weighted automaton that gives weight `K::one()` to every
+    /// string accepted by the DFA and `K::zero()` to every other string.
+    ///
+    /// Over the `semiring::Boolean` semiring the result recognizes exactly the
+    /// language of the DFA.
+    pub fn from_dfa<S>(dfa: &DFA<A, S>) -> Self
+    where
+        S: DFAStorage<A>,
+        S::State: Ord + Hash,
+        A: Clone,
+    {
+        let mut weighted = WeightedAutomaton::new(dfa.alphabet().clone());
+        let mut state_mapping = HashMap::new();
+
+        for state in dfa.storage().all_states() {
+            state_mapping.insert(state, weighted.new_state());
+        }
+
+        for (from, sym, to) in dfa.storage().all_transitions() {
+            weighted.transitions[state_mapping[&from]].push((sym, K::one(), state_mapping[&to]));
+        }
+
+        for accept in dfa.accept_states() {
+            weighted.final_weight[state_mapping[accept]] = K::one();
+        }
+
+        weighted.start = Some(state_mapping[dfa.start_state()]);
+
+        weighted
+    }
+
+    /// Return a reference to the `Alphabet` of this automaton.
+    pub fn alphabet(&self) -> &A {
+        &self.alphabet
+    }
+
+    /// Return the number of states in this automaton.
+    pub fn num_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Record and return a new state, with a final weight of `K::zero()`.
+    pub fn new_state(&mut self) -> usize {
+        self.transitions.push(Vec::new());
+        self.final_weight.push(K::zero());
+
+        self.transitions.len() - 1
+    }
+
+    /// Set the starting state.
+    ///
+    /// # Error
+    ///
+    /// This function will error if the state is not valid.
+    pub fn start_state(&mut self, start: usize) -> Result<&mut Self, Error> {
+        self.check_state(start)?;
+        self.start = Some(start);
+
+        Ok(self)
+    }
+
+    /// Set the weight of ending a string in the given state. States with a
+    /// final weight of `K::zero()` are not accepting.
+    ///
+    /// # Error
+    ///
+    /// This function will error if the state is not valid.
+    pub fn final_weight(&mut self, state: usize, weight: K) -> Result<&mut Self, Error> {
+        self.check_state(state)?;
+        self.final_weight[state] = weight;
+
+        Ok(self)
+    }
+
+    /// Record and validate a new weighted transition.
+    ///
+    /// # Error
+    ///
+    /// This function will error if the symbol was not a member of the
+    /// alphabet, or if either the `from` or `to` state is not valid.
+    pub fn transition(
+        &mut self,
+        from: usize,
+        sym: A::Symbol,
+        weight: K,
+        to: usize,
+    ) -> Result<(), Error>
+    where
+        A::Symbol: Debug,
+    {
+        self.check_state(from)?;
+        self.check_state(to)?;
+
+        if !self.alphabet.contains(&sym) {
+            return Err(Error::symbol_not_in_alphabet(&sym));
+        }
+
+        self.transitions[from].push((sym, weight, to));
+
+        Ok(())
+    }
+
+    /// Compute the weight of the given string.
+    ///
+    /// Strings that contain a symbol not in the alphabet, and strings given to
+    /// an automaton without a start state, have weight `K::zero()`.
+    pub fn weight<I: IntoIterator<Item = A::Symbol>>(&self, string: I) -> K {
+        let start = match self.start {
+            Some(start) => start,
+            None => return K::zero(),
+        };
+
+        let mut current = vec![K::zero(); self.num_states()];
+        current[start] = K::one();
+
+        for sym in string {
+            if !self.alphabet.contains(&sym) {
+                return K::zero();
+            }
+
+            let mut next = vec![K::zero(); self.num_states()];
+            for (state, weight) in current.iter().enumerate() {
+                if weight.is_zero() {
+                    continue;
+                }
+
+                for (trans_sym, trans_weight, to) in &self.transitions[state] {
+                    if *trans_sym == sym {
+                        next[*to] = next[*to].plus(&weight.times(trans_weight));
+                    }
+                }
+            }
+
+            current = next;
+        }
+
+        current
+            .iter()
+            .zip(&self.final_weight)
+            .fold(K::zero(), |acc, (weight, final_weight)| {
+                acc.plus(&weight.times(final_weight))
+            })
+    }
+
+    fn check_state(&self, state: usize) -> Result<(), Error> {
+        if state < self.num_states() {
+            Ok(())
+        } else {
+            Err(Error::invalid_state(&state))
+        }
+    }
+}
+
+impl<A, K> WeightedAutomaton<A, K>
+where
+    A: Alphabet,
+    A::Symbol: PartialEq,
+    K: Idempotent,
+{
+    /// Compute, for every state, the `plus` over all paths from the start
+    /// state of the path weight.
+    ///
+    /// This is the generic single-source shortest-distance algorithm of Mohri,
+    /// which terminates for idempotent semirings as long as no cycle improves
+    /// on the empty path (e.g. no negative cycles in the `Tropical` semiring).
+    /// The result is indexed by state.
+    pub fn shortest_distance(&self) -> Vec<K> {
+        let mut distance = vec![K::zero(); self.num_states()];
+        let start = match self.start {
+            Some(start) => start,
+            None => return distance,
+        };
+
+        let mut residual = vec![K::zero(); self.num_states()];
+        let mut in_queue = vec![false; self.num_states()];
+        let mut queue = VecDeque::new();
+
+        distance[start] = K::one();
+        residual[start] = K::one();
+        in_queue[start] = true;
+        queue.push_back(start);
+
+        while let Some(state) = queue.pop_front() {
+            in_queue[state] = false;
+            let weight = core::mem::replace(&mut residual[state], K::zero());
+
+            for (_, trans_weight, to) in &self.transitions[state] {
+                let candidate = weight.times(trans_weight);
+                let updated = distance[*to].plus(&candidate);
+
+                if updated != distance[*to] {
+                    distance[*to] = updated;
+                    residual[*to] = residual[*to].plus(&candidate);
+
+                    if !in_queue[*to] {
+                        in_queue[*to] = true;
+                        queue.push_back(*to);
+                    }
+                }
+            }
+        }
+
+        distance
+    }
+
+    /// Compute the `plus` of the weights of every string, which for the
+    /// `Tropical` semiring is the cost of the cheapest accepted string.
+    pub fn total_weight(&self) -> K {
+        self.shortest_distance()
+            .iter()
+            .zip(&self.final_weight)
+            .fold(K::zero(), |acc, (distance, final_weight)| {
+                acc.plus(&distance.times(final_weight))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        semiring::{self, Counting, Tropical},
+        test_helper::CONTAINS_TWO_FALSE_DFA,
+    };
+
+    // Edit distance 1 from "ab" over {a, b}, costs are the number of edits
+    fn edit_cost_automaton() -> WeightedAutomaton<crate::Range<char>, Tropical> {
+        let mut wfa = WeightedAutomaton::new('a'..='b');
+        let s0 = wfa.new_state();
+        let s1 = wfa.new_state();
+        let s2 = wfa.new_state();
+
+        wfa.transition(s0, 'a', Tropical(0.0), s1).unwrap();
+        wfa.transition(s0, 'b', Tropical(1.0), s1).unwrap();
+        wfa.transition(s1, 'b', Tropical(0.0), s2).unwrap();
+        wfa.transition(s1, 'a', Tropical(1.0), s2).unwrap();
+        wfa.start_state(s0).unwrap();
+        wfa.final_weight(s2, Tropical(0.0)).unwrap();
+
+        wfa
+    }
+
+    #[test]
+    fn tropical_string_weights() {
+        let wfa = edit_cost_automaton();
+
+        assert_eq!(wfa.weight("ab".chars()), Tropical(0.0));
+        assert_eq!(wfa.weight("bb".chars()), Tropical(1.0));
+        assert_eq!(wfa.weight("ba".chars()), Tropical(2.0));
+        assert_eq!(wfa.weight("a".chars()), Tropical::zero());
+        assert_eq!(wfa.weight("az".chars()), Tropical::zero());
+        assert_eq!(wfa.total_weight(), Tropical(0.0));
+    }
+
+    #[test]
+    fn counting_paths() {
+        let mut wfa = WeightedAutomaton::new(alphabet::Unit);
+        let s0 = wfa.new_state();
+        let s1 = wfa.new_state();
+        let s2 = wfa.new_state();
+
+        wfa.transition(s0, (), Counting(1), s1).unwrap();
+        wfa.transition(s0, (), Counting(1), s2).unwrap();
+        wfa.transition(s0, (), Counting(3), s2).unwrap();
+        wfa.start_state(s0).unwrap();
+        wfa.final_weight(s1, Counting(1)).unwrap();
+        wfa.final_weight(s2, Counting(1)).unwrap();
+
+        assert_eq!(wfa.weight(vec![()]), Counting(5));
+        assert_eq!(wfa.weight(vec![]), Counting(0));
+        assert!(wfa.transition(s0, (), Counting(1), 7).is_err());
+    }
+
+    #[test]
+    fn boolean_from_dfa_agrees() {
+        let wfa: WeightedAutomaton<_, semiring::Boolean> =
+            WeightedAutomaton::from_dfa(&*CONTAINS_TWO_FALSE_DFA);
+
+        for string in &[
+            vec![],
+            vec![false, false],
+            vec![true, false, true, false],
+            vec![true, false, false, true],
+        ] {
+            assert_eq!(
+                wfa.weight(string.iter().copied()).0,
+                CONTAINS_TWO_FALSE_DFA.accept(string.iter().copied())
+            );
+        }
+        assert_eq!(wfa.total_weight(), semiring::Boolean(true));
+    }
+}
@@ -0,0 +1,19 @@
+/// Generated by `regular` from a DFA with 1 states.
+#[allow(unreachable_patterns, unused_variables)]
+pub fn dead_start_match<I: IntoIterator<Item = bool>>(string: I) -> bool {
+    const ACCEPT: [bool; 1] = [true];
+    fn step(state: usize, sym: bool) -> Option<usize> {
+        None
+    }
+    let mut state: usize = 0;
+    for sym in string {
+        if state == 0 {
+            break;
+        }
+        state = match step(state, sym) {
+            Some(next) => next,
+            None => return false,
+        };
+    }
+    ACCEPT[state]
+}
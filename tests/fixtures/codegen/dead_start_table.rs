@@ -0,0 +1,27 @@
+/// Generated by `regular` from a DFA with 1 states.
+#[allow(unreachable_patterns, unused_variables)]
+pub fn dead_start_table<I: IntoIterator<Item = bool>>(string: I) -> bool {
+    const ACCEPT: [bool; 1] = [true];
+    const NONE: u8 = u8::MAX;
+    static TABLE: [[u8; 0]; 1] = [
+        [],
+    ];
+    fn class(sym: bool) -> Option<usize> {
+        None
+    }
+    let mut state: usize = 0;
+    for sym in string {
+        if state == 0 {
+            break;
+        }
+        let class = match class(sym) {
+            Some(class) => class,
+            None => return false,
+        };
+        state = match TABLE[state][class] {
+            NONE => return false,
+            next => next as usize,
+        };
+    }
+    ACCEPT[state]
+}
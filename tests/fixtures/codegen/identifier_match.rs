@@ -0,0 +1,26 @@
+/// Generated by `regular` from a DFA with 2 states.
+#[allow(unreachable_patterns, unused_variables)]
+pub fn identifier_match<I: IntoIterator<Item = char>>(string: I) -> bool {
+    const ACCEPT: [bool; 2] = [false, true];
+    fn step(state: usize, sym: char) -> Option<usize> {
+        Some(match state {
+            0 => match sym {
+                'a'..='z' => 1,
+                _ => return None,
+            },
+            1 => match sym {
+                '0'..='9' | 'a'..='z' => 1,
+                _ => return None,
+            },
+            _ => return None,
+        })
+    }
+    let mut state: usize = 0;
+    for sym in string {
+        state = match step(state, sym) {
+            Some(next) => next,
+            None => return false,
+        };
+    }
+    ACCEPT[state]
+}
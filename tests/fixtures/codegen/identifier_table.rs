@@ -0,0 +1,29 @@
+/// Generated by `regular` from a DFA with 2 states.
+#[allow(unreachable_patterns, unused_variables)]
+pub fn identifier_table<I: IntoIterator<Item = char>>(string: I) -> bool {
+    const ACCEPT: [bool; 2] = [false, true];
+    const NONE: u8 = u8::MAX;
+    static TABLE: [[u8; 2]; 2] = [
+        [NONE, 1],
+        [1, 1],
+    ];
+    fn class(sym: char) -> Option<usize> {
+        Some(match sym {
+            '0'..='9' => 0,
+            'a'..='z' => 1,
+            _ => return None,
+        })
+    }
+    let mut state: usize = 0;
+    for sym in string {
+        let class = match class(sym) {
+            Some(class) => class,
+            None => return false,
+        };
+        state = match TABLE[state][class] {
+            NONE => return false,
+            next => next as usize,
+        };
+    }
+    ACCEPT[state]
+}
@@ -0,0 +1,35 @@
+/// Generated by `regular` from a DFA with 3 states.
+#[allow(unreachable_patterns, unused_variables)]
+pub fn two_false_match<I: IntoIterator<Item = bool>>(string: I) -> bool {
+    const ACCEPT: [bool; 3] = [false, false, true];
+    fn step(state: usize, sym: bool) -> Option<usize> {
+        Some(match state {
+            0 => match sym {
+                false => 1,
+                true => 0,
+                _ => return None,
+            },
+            1 => match sym {
+                false => 2,
+                true => 0,
+                _ => return None,
+            },
+            2 => match sym {
+                false | true => 2,
+                _ => return None,
+            },
+            _ => return None,
+        })
+    }
+    let mut state: usize = 0;
+    for sym in string {
+        if state == 2 {
+            break;
+        }
+        state = match step(state, sym) {
+            Some(next) => next,
+            None => return false,
+        };
+    }
+    ACCEPT[state]
+}
@@ -0,0 +1,33 @@
+/// Generated by `regular` from a DFA with 3 states.
+#[allow(unreachable_patterns, unused_variables)]
+pub fn two_false_table<I: IntoIterator<Item = bool>>(string: I) -> bool {
+    const ACCEPT: [bool; 3] = [false, false, true];
+    const NONE: u8 = u8::MAX;
+    static TABLE: [[u8; 2]; 3] = [
+        [1, 0],
+        [2, 0],
+        [2, 2],
+    ];
+    fn class(sym: bool) -> Option<usize> {
+        Some(match sym {
+            false => 0,
+            true => 1,
+            _ => return None,
+        })
+    }
+    let mut state: usize = 0;
+    for sym in string {
+        if state == 2 {
+            break;
+        }
+        let class = match class(sym) {
+            Some(class) => class,
+            None => return false,
+        };
+        state = match TABLE[state][class] {
+            NONE => return false,
+            next => next as usize,
+        };
+    }
+    ACCEPT[state]
+}